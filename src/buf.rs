@@ -0,0 +1,130 @@
+//! An owned UID together with its preformatted canonical string.
+
+use ::std::{fmt, ops::Deref, str::FromStr};
+
+use crate::uid::{SwissUid, UidError, CANONICAL_DIGIT_POS};
+
+/// A [`SwissUid`] which stores its canonical string representation.
+///
+/// The string is formatted once on construction, so Display heavy code paths
+/// (template rendering, logging) can borrow it with [`SwissUidBuf::as_str`]
+/// instead of formatting the same UID over and over again.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::buf::SwissUidBuf;
+/// use swiss_uid::uid::SwissUid;
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// let buf = SwissUidBuf::new(uid);
+/// assert_eq!(buf.as_str(), "CHE-109.322.551");
+/// assert_eq!(buf.uid(), uid);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SwissUidBuf {
+    uid: SwissUid,
+    buf: [u8; 15],
+}
+
+impl SwissUidBuf {
+    /// Formats the UID into a new buffer.
+    pub fn new(uid: SwissUid) -> Self {
+        // The group separators are the bytes not overwritten below
+        let mut buf = [b'.'; 15];
+        let pfx = uid.category_code().as_bytes();
+        buf[..pfx.len()].copy_from_slice(pfx);
+        buf[pfx.len()] = b'-';
+        for (&i, d) in CANONICAL_DIGIT_POS.iter().zip(uid.digits()) {
+            buf[i] = b'0' + d;
+        }
+        Self { uid, buf }
+    }
+
+    /// Returns the canonical string, e.g. `CHE-109.322.551`.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: The buffer only ever contains ASCII characters written by `new`
+        unsafe { ::std::str::from_utf8_unchecked(&self.buf) }
+    }
+
+    /// Returns the canonical string as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the contained UID.
+    pub fn uid(&self) -> SwissUid {
+        self.uid
+    }
+}
+
+impl From<SwissUid> for SwissUidBuf {
+    fn from(uid: SwissUid) -> Self {
+        Self::new(uid)
+    }
+}
+
+impl From<SwissUidBuf> for SwissUid {
+    fn from(buf: SwissUidBuf) -> Self {
+        buf.uid
+    }
+}
+
+impl FromStr for SwissUidBuf {
+    type Err = UidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self::new)
+    }
+}
+
+impl Deref for SwissUidBuf {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SwissUidBuf {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for SwissUidBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.uid, f)
+    }
+}
+
+impl fmt::Display for SwissUidBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_buf_matches_display() {
+        for s in ["CHE-109.322.551", "ADM-100.002.005", "CHE-999.999.996"] {
+            let uid = SwissUid::new(s).unwrap();
+            let buf = SwissUidBuf::from(uid);
+            assert_eq!(buf.as_str(), uid.to_string());
+            assert_eq!(buf.to_string(), s);
+            assert_eq!(format!("{:?}", buf), format!("{:?}", uid));
+            assert_eq!(SwissUid::from(buf), uid);
+        }
+    }
+
+    #[test]
+    fn test_buf_from_str() {
+        let buf: SwissUidBuf = "che 109 322 551".parse().unwrap();
+        assert_eq!(&*buf, "CHE-109.322.551");
+        assert_eq!(buf.len(), 15);
+        assert!("CHE-109.322.552".parse::<SwissUidBuf>().is_err());
+    }
+}
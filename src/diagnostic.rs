@@ -0,0 +1,140 @@
+//! Plain text rendering of parse failures for terminal tools.
+
+use crate::uid::UidError;
+
+impl UidError {
+    /// Renders the error as a multi-line annotated snippet of the `input` which
+    /// failed to parse, with a caret under the offending character and a hint.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let input = "CHE-109.322.552";
+    /// let err = SwissUid::new(input).unwrap_err();
+    /// assert_eq!(
+    ///     err.render_diagnostic(input),
+    ///     "error: Mismatched check digit: Calculated check digit is [1]\n\
+    ///      \x20 |\n\
+    ///      \x20 | CHE-109.322.552\n\
+    ///      \x20 |               ^ expected check digit 1"
+    /// );
+    /// ```
+    pub fn render_diagnostic(&self, input: &str) -> String {
+        format!(
+            "error: {}\n  |\n  | {}\n  | {:>width$} {}",
+            self,
+            input,
+            "^",
+            self.hint(input),
+            width = self.position() + 1
+        )
+    }
+
+    /// Returns a short hint about the error for the annotation.
+    fn hint(&self, input: &str) -> String {
+        match self {
+            // Errors pointing past the end of the input are about missing characters
+            UidError::InvalidFormat(_, position) if *position >= input.chars().count() => {
+                let found = input.chars().filter(char::is_ascii_digit).count();
+                format!("expected 9 digits, found {}", found)
+            }
+            UidError::InvalidFormat(message, _) => {
+                let mut hint = message.to_string();
+                // Keep acronyms such as `UID` in uppercase
+                if !hint.chars().nth(1).is_some_and(|c| c.is_ascii_uppercase()) {
+                    if let Some(first) = hint.get_mut(..1) {
+                        first.make_ascii_lowercase();
+                    }
+                }
+                hint
+            }
+            UidError::LeadingZeroNotAllowed(_) => "the first digit must not be 0".to_owned(),
+            UidError::InvalidCheckDigit(_, _) => {
+                "no valid UID has these 8 digits (check digit would be 10)".to_owned()
+            }
+            UidError::MismatchedCheckDigit(m, _) => {
+                format!("expected check digit {}", m.expected())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse::ParseOptions, uid::SwissUid};
+
+    fn render(input: &str) -> String {
+        SwissUid::new(input).unwrap_err().render_diagnostic(input)
+    }
+
+    #[test]
+    fn test_render_prefix() {
+        assert_eq!(
+            render("ABC-109.322.551"),
+            "error: Invalid format: Prefix must be 'CHE' or 'ADM'\n  |\n  | ABC-109.322.551\n  \
+             | ^ prefix must be 'CHE' or 'ADM'"
+        );
+    }
+
+    #[test]
+    fn test_render_leading_zero() {
+        assert_eq!(
+            render("CHE-010.322.557"),
+            "error: Leading zero is not allowed\n  |\n  | CHE-010.322.557\n  \
+             |     ^ the first digit must not be 0"
+        );
+    }
+
+    #[test]
+    fn test_render_missing_digits() {
+        assert_eq!(
+            render("CHE-109.322"),
+            "error: Invalid format: UID must have 9 digits\n  |\n  | CHE-109.322\n  \
+             |            ^ expected 9 digits, found 6"
+        );
+    }
+
+    #[test]
+    fn test_render_trailing_characters() {
+        let input = "CHE-109.322.551 XY";
+        let options = ParseOptions {
+            reject_trailing: true,
+            ..Default::default()
+        };
+        let err = SwissUid::parse_with(input, &options).unwrap_err();
+        assert_eq!(
+            err.render_diagnostic(input),
+            "error: Invalid format: Unexpected characters after the check digit\n  |\n  \
+             | CHE-109.322.551 XY\n  \
+             |                 ^ unexpected characters after the check digit"
+        );
+    }
+
+    #[test]
+    fn test_render_invalid_checkdigit() {
+        assert_eq!(
+            render("CHE-100.000.160"),
+            "error: Invalid check digit: 10\n  |\n  | CHE-100.000.160\n  \
+             |               ^ no valid UID has these 8 digits (check digit would be 10)"
+        );
+    }
+
+    #[test]
+    fn test_position() {
+        for (input, position) in [
+            ("ABC-109.322.551", 0),
+            ("C", 0),
+            ("CHE-010.322.557", 4),
+            ("che 109 322 552", 14),
+            ("CHE-109.322.552 MWST", 14),
+            ("CHE-100.000.160", 14),
+            ("CHE-109.322", 11),
+            ("\u{feff}CHE-109.322.552", 15),
+        ] {
+            let err = SwissUid::new(input).unwrap_err();
+            assert_eq!(err.position(), position, "{}", input);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Helpers for the UID in UN/EDIFACT messages.
+//!
+//! The VAT registration number of a party is carried in an `RFF` segment with
+//! the reference qualifier `VA`, placed in the segment group of the party's
+//! `NAD` segment, e.g. `NAD+SE+...'RFF+VA:CHE-109.322.551'`.
+//!
+//! The segments are formatted with the default separators. When reading an
+//! interchange, the separators declared in a leading `UNA` segment are used
+//! and characters escaped with the release character (`?` by default) are
+//! taken literally.
+
+use crate::uid::{SwissUid, UidError};
+
+/// Reference code qualifier of the VAT registration number.
+pub const QUALIFIER_VAT: &str = "VA";
+
+/// The service characters separating the parts of an interchange.
+#[derive(Debug, Clone, Copy)]
+struct ServiceChars {
+    component: char,
+    element: char,
+    release: Option<char>,
+    terminator: char,
+}
+
+impl ServiceChars {
+    /// The service characters used without a `UNA` segment.
+    const DEFAULT: Self = Self {
+        component: ':',
+        element: '+',
+        release: Some('?'),
+        terminator: '\'',
+    };
+
+    /// Returns the service characters of the interchange and the byte offset
+    /// of its first segment after the optional `UNA` segment.
+    fn of_interchange(interchange: &str) -> Result<(Self, usize), UidError> {
+        let Some(advice) = interchange.strip_prefix("UNA") else {
+            return Ok((Self::DEFAULT, 0));
+        };
+        // Component and data element separator, decimal mark, release
+        // character, a reserved character and the segment terminator
+        let mut chars = advice.chars();
+        let [Some(component), Some(element), Some(_), Some(release), Some(_), Some(terminator)] =
+            [(); 6].map(|_| chars.next())
+        else {
+            return Err(UidError::InvalidFormat(
+                "Service string advice UNA is incomplete",
+                interchange.chars().count(),
+            ));
+        };
+        let service = Self {
+            component,
+            element,
+            // A space declares that no release character is used
+            release: (release != ' ').then_some(release),
+            terminator,
+        };
+        Ok((service, interchange.len() - chars.as_str().len()))
+    }
+}
+
+/// Returns the UID as a terminated `RFF+VA` segment.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{edifact, uid::SwissUid};
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(edifact::to_rff_segment(&uid), "RFF+VA:CHE-109.322.551'");
+/// ```
+pub fn to_rff_segment(uid: &SwissUid) -> String {
+    format!("RFF+{}:{}'", QUALIFIER_VAT, uid)
+}
+
+/// Returns a `NAD` segment followed by the `RFF+VA` segment of the UID.
+///
+/// `nad` is the complete `NAD` segment, with or without its terminator.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{edifact, uid::SwissUid};
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(
+///     edifact::to_nad_group("NAD+SE+7612345000015::9'", &uid),
+///     "NAD+SE+7612345000015::9'RFF+VA:CHE-109.322.551'"
+/// );
+/// ```
+pub fn to_nad_group(nad: &str, uid: &SwissUid) -> String {
+    format!(
+        "{}'{}",
+        nad.strip_suffix('\'').unwrap_or(nad),
+        to_rff_segment(uid)
+    )
+}
+
+/// Parses and validates the UID of an `RFF` segment.
+///
+/// Returns `Ok(None)` if the segment carries a reference with a qualifier other
+/// than `VA`. The position of an error refers to the characters of `segment`.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::edifact;
+///
+/// let uid = edifact::parse_rff_segment("RFF+VA:CHE-109.322.551'").unwrap();
+/// assert_eq!(uid.unwrap().to_string(), "CHE-109.322.551");
+/// assert_eq!(edifact::parse_rff_segment("RFF+ON:4711'"), Ok(None));
+/// ```
+pub fn parse_rff_segment(segment: &str) -> Result<Option<SwissUid>, UidError> {
+    parse_rff(segment, &ServiceChars::DEFAULT)
+}
+
+/// Parses an `RFF` segment with the given service characters.
+fn parse_rff(segment: &str, service: &ServiceChars) -> Result<Option<SwissUid>, UidError> {
+    let segment = segment.trim_end();
+    let segment = segment.strip_suffix(service.terminator).unwrap_or(segment);
+    let reference = segment
+        .strip_prefix("RFF")
+        .and_then(|s| s.strip_prefix(service.element))
+        .ok_or(UidError::InvalidFormat("Segment must start with 'RFF+'", 0))?;
+
+    let mut components = split_released(reference, service.component, service.release);
+    if components.next().map(|(_, qualifier)| qualifier) != Some(QUALIFIER_VAT) {
+        return Ok(None);
+    }
+    match components.next() {
+        Some((start, value)) if !value.is_empty() => {
+            let offset = segment[..segment.len() - reference.len() + start]
+                .chars()
+                .count();
+            SwissUid::new(value).map(Some).map_err(|e| e.offset(offset))
+        }
+        _ => Err(UidError::InvalidFormat(
+            "Segment RFF+VA is missing its value",
+            segment.chars().count(),
+        )),
+    }
+}
+
+/// Finds the first `RFF+VA` segment of an interchange and validates its UID.
+///
+/// Returns `Ok(None)` if the interchange contains no such segment. The
+/// separators of a leading `UNA` segment are honoured, as are characters
+/// escaped with the release character. The position of an error refers to the
+/// characters of `interchange`.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::edifact;
+///
+/// let msg = "UNH+1+INVOIC:D:96A:UN'NAD+SE+7612345000015::9'RFF+VA:CHE-109.322.551'UNT+4+1'";
+/// let uid = edifact::find_vat_reference(msg).unwrap();
+/// assert_eq!(uid.unwrap().to_string(), "CHE-109.322.551");
+/// ```
+pub fn find_vat_reference(interchange: &str) -> Result<Option<SwissUid>, UidError> {
+    let (service, body) = ServiceChars::of_interchange(interchange)?;
+    for (start, raw) in split_released(&interchange[body..], service.terminator, service.release) {
+        let segment = raw.trim_start();
+        let offset = body + start + raw.len() - segment.len();
+        let is_rff = segment
+            .strip_prefix("RFF")
+            .is_some_and(|s| s.starts_with(service.element));
+        if !is_rff {
+            continue;
+        }
+        match parse_rff(segment, &service) {
+            Ok(None) => continue,
+            result => return result.map_err(|e| e.offset(interchange[..offset].chars().count())),
+        }
+    }
+    Ok(None)
+}
+
+/// Splits `s` on `separator`, except where it is escaped with the `release`
+/// character, and returns each part with its byte offset in `s`.
+fn split_released(
+    s: &str,
+    separator: char,
+    release: Option<char>,
+) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = Some(0);
+    std::iter::from_fn(move || {
+        let offset = start?;
+        let rest = &s[offset..];
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            if Some(c) == release {
+                chars.next();
+            } else if c == separator {
+                start = Some(offset + i + c.len_utf8());
+                return Some((offset, &rest[..i]));
+            }
+        }
+        start = None;
+        Some((offset, rest))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rff_roundtrip() {
+        let uid = SwissUid::new("ADM-100.000.029").unwrap();
+        assert_eq!(parse_rff_segment(&to_rff_segment(&uid)), Ok(Some(uid)));
+    }
+
+    #[test]
+    fn test_parse_rff_invalid() {
+        assert_eq!(
+            parse_rff_segment("NAD+SE+7612345000015::9'"),
+            Err(UidError::InvalidFormat("Segment must start with 'RFF+'", 0))
+        );
+        assert_eq!(
+            parse_rff_segment("RFF+VA'"),
+            Err(UidError::InvalidFormat(
+                "Segment RFF+VA is missing its value",
+                6
+            ))
+        );
+        assert_eq!(
+            parse_rff_segment("RFF+VA:CHE-109.322.552'").map_err(|e| e.position()),
+            Err(21)
+        );
+    }
+
+    #[test]
+    fn test_find_vat_reference() {
+        let msg = "NAD+BY+7600000000000::9'\nRFF+API:123'\nRFF+VA:CHE-109.322.551'";
+        assert_eq!(
+            find_vat_reference(msg),
+            Ok(Some(SwissUid::new("CHE-109.322.551").unwrap()))
+        );
+        assert_eq!(find_vat_reference("NAD+BY+7600000000000::9'"), Ok(None));
+
+        let msg = "NAD+BY+7600000000000::9'\nRFF+VA:CHE-010.322.557'";
+        assert_eq!(
+            find_vat_reference(msg),
+            Err(UidError::LeadingZeroNotAllowed(36))
+        );
+    }
+
+    #[test]
+    fn test_find_vat_reference_with_release_character() {
+        // The released apostrophe is part of the free text, not a terminator
+        let msg = "FTX+AAI+++see?'RFF+VA:CHE-109.322.552'RFF+VA:CHE-109.322.551'";
+        assert_eq!(
+            find_vat_reference(msg),
+            Ok(Some(SwissUid::new("CHE-109.322.551").unwrap()))
+        );
+        assert_eq!(
+            find_vat_reference("FTX+AAI+++see?'RFF+VA:CHE-109.322.552"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_find_vat_reference_with_una() {
+        let msg = "UNA|^.? ~NAD^SE^7612345000015||9~\nRFF^VA|CHE-109.322.551~";
+        assert_eq!(
+            find_vat_reference(msg),
+            Ok(Some(SwissUid::new("CHE-109.322.551").unwrap()))
+        );
+        // The default terminator has no meaning once UNA declares another one
+        let msg = "UNA:+.? ~FTX+AAI+++it's'RFF+VA:CHE-109.322.551~";
+        assert_eq!(find_vat_reference(msg), Ok(None));
+
+        let msg = "UNA:+.? 'RFF+VA:CHE-010.322.557'";
+        assert_eq!(
+            find_vat_reference(msg),
+            Err(UidError::LeadingZeroNotAllowed(20))
+        );
+        assert_eq!(
+            find_vat_reference("UNA:+"),
+            Err(UidError::InvalidFormat(
+                "Service string advice UNA is incomplete",
+                5
+            ))
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Step by step explanation of the check digit calculation.
+
+use ::std::fmt;
+
+use crate::uid::{UidError, DIGIT_FACTORS};
+
+/// The intermediate steps of the modulo 11 check digit calculation of the
+/// 8 main digits of a UID.
+///
+/// The `Display` impl renders the steps as a small table, e.g. for training
+/// material or to show users why a UID is invalid.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::SwissUid;
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// let explanation = uid.explain_checkdigit();
+/// assert_eq!(explanation.products(), [5, 0, 27, 6, 14, 12, 25, 20]);
+/// assert_eq!(explanation.sum(), 109);
+/// assert_eq!(explanation.remainder(), 10);
+/// assert_eq!(explanation.checkdigit(), Ok(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckDigitExplanation {
+    digits: [u8; 8],
+    products: [u8; 8],
+    sum: u32,
+}
+
+impl CheckDigitExplanation {
+    /// Returns the 8 main digits the check digit is calculated from.
+    pub fn digits(&self) -> [u8; 8] {
+        self.digits
+    }
+
+    /// Returns the factor of each position as defined by eCH-0097.
+    pub fn factors(&self) -> [u8; 8] {
+        DIGIT_FACTORS
+    }
+
+    /// Returns the product of each digit with the factor of its position.
+    pub fn products(&self) -> [u8; 8] {
+        self.products
+    }
+
+    /// Returns the sum of all products.
+    pub fn sum(&self) -> u32 {
+        self.sum
+    }
+
+    /// Returns the sum modulo 11.
+    pub fn remainder(&self) -> u8 {
+        (self.sum % 11) as u8
+    }
+
+    /// Returns the resulting check digit `11 - remainder`, where 11 maps to 0
+    /// and 10 means that no valid UID has these main digits.
+    pub fn checkdigit(&self) -> Result<u8, UidError> {
+        match 11 - self.remainder() {
+            11 => Ok(0),
+            10 => Err(UidError::InvalidCheckDigit(10, 8)),
+            n => Ok(n),
+        }
+    }
+}
+
+impl fmt::Display for CheckDigitExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "digits:  ")?;
+        for d in self.digits {
+            write!(f, " {:>2}", d)?;
+        }
+        write!(f, "\nfactors: ")?;
+        for d in DIGIT_FACTORS {
+            write!(f, " {:>2}", d)?;
+        }
+        write!(f, "\nproducts:")?;
+        for d in self.products {
+            write!(f, " {:>2}", d)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "sum: {}", self.sum)?;
+        writeln!(f, "{} mod 11 = {}", self.sum, self.remainder())?;
+        writeln!(f, "11 - {} = {}", self.remainder(), 11 - self.remainder())?;
+        match self.checkdigit() {
+            Ok(p) => write!(f, "check digit: {}", p),
+            Err(_) => write!(f, "check digit: none, 10 is not a valid check digit"),
+        }
+    }
+}
+
+/// Explains the check digit calculation for the given 8 main digits, which do
+/// not need to belong to a valid UID.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::explain::explain_checkdigit;
+/// use swiss_uid::uid::UidError;
+///
+/// let explanation = explain_checkdigit(&[1, 0, 0, 0, 0, 0, 1, 6]).unwrap();
+/// assert_eq!(explanation.checkdigit(), Err(UidError::InvalidCheckDigit(10, 8)));
+/// ```
+pub fn explain_checkdigit(main_digits: &[u8]) -> Result<CheckDigitExplanation, UidError> {
+    let digits: [u8; 8] = main_digits
+        .try_into()
+        .map_err(|_| UidError::InvalidFormat("UID must have 8 digits", main_digits.len().min(8)))?;
+    if let Some(i) = digits.iter().position(|&d| d > 9) {
+        return Err(UidError::InvalidFormat(
+            "UID digits must be between 0 and 9",
+            i,
+        ));
+    }
+
+    let mut products = [0u8; 8];
+    for ((p, d), f) in products.iter_mut().zip(digits).zip(DIGIT_FACTORS) {
+        *p = d * f;
+    }
+    Ok(CheckDigitExplanation {
+        digits,
+        products,
+        sum: products.iter().map(|&p| p as u32).sum(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::uid::calculate_checkdigit;
+
+    #[test]
+    fn test_explanation_matches_calculation() {
+        for digits in [
+            [1, 0, 9, 3, 2, 2, 5, 5],
+            [1, 0, 0, 0, 0, 2, 0, 0],
+            [1, 0, 0, 0, 0, 0, 1, 6],
+            [9, 9, 9, 9, 9, 9, 9, 9],
+        ] {
+            let explanation = explain_checkdigit(&digits).unwrap();
+            assert_eq!(explanation.checkdigit(), calculate_checkdigit(&digits));
+        }
+    }
+
+    #[test]
+    fn test_explanation_display() {
+        let explanation = explain_checkdigit(&[1, 0, 9, 3, 2, 2, 5, 5]).unwrap();
+        assert_eq!(
+            explanation.to_string(),
+            "digits:    1  0  9  3  2  2  5  5\n\
+             factors:   5  4  3  2  7  6  5  4\n\
+             products:  5  0 27  6 14 12 25 20\n\
+             sum: 109\n\
+             109 mod 11 = 10\n\
+             11 - 10 = 1\n\
+             check digit: 1"
+        );
+    }
+
+    #[test]
+    fn test_explanation_invalid_input() {
+        assert_eq!(
+            explain_checkdigit(&[1, 0, 9]),
+            Err(UidError::InvalidFormat("UID must have 8 digits", 3))
+        );
+        assert_eq!(
+            explain_checkdigit(&[1, 0, 9, 3, 2, 2, 5, 10]),
+            Err(UidError::InvalidFormat(
+                "UID digits must be between 0 and 9",
+                7
+            ))
+        );
+    }
+}
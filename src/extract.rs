@@ -0,0 +1,428 @@
+//! Extraction of UIDs from free text and byte streams.
+//!
+//! A UID is recognized if it starts at a word boundary with the prefix `CHE` or
+//! `ADM` (in any case), optionally followed by `-` or a space, and the 9 digits
+//! in groups of three, optionally separated by `.` or a space. Candidates with
+//! an invalid check digit are skipped.
+
+use ::std::{
+    collections::HashSet,
+    io::{self, Read},
+    iter::FusedIterator,
+};
+
+use crate::{
+    stats::ValidationStats,
+    uid::{SwissUid, UidError},
+};
+
+/// Length of the longest recognized UID (`CHE-109.322.551`) plus one byte of
+/// lookahead to make sure no further digit follows.
+const WINDOW: usize = 16;
+
+/// Size of the chunks read from the underlying reader.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Returns an iterator over all valid UIDs found in `text`.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::extract::find_uids;
+///
+/// let text = "Supplier CHE-109.322.551 (formerly che 100 002 005), not CHE-109.322.552";
+/// let uids: Vec<String> = find_uids(text).map(|uid| uid.to_string()).collect();
+/// assert_eq!(uids, ["CHE-109.322.551", "CHE-100.002.005"]);
+/// ```
+pub fn find_uids(text: &str) -> impl Iterator<Item = SwissUid> + '_ {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    ::std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            let at_boundary = pos == 0 || !bytes[pos - 1].is_ascii_alphanumeric();
+            if let Some((len, res)) = at_boundary.then(|| match_at(&bytes[pos..])).flatten() {
+                pos += len;
+                if let Ok(uid) = res {
+                    return Some(uid);
+                }
+            } else {
+                pos += 1;
+            }
+        }
+        None
+    })
+}
+
+/// A pull parser yielding the valid UIDs found in any [`Read`] source.
+///
+/// The source is read in chunks and UIDs spanning chunk boundaries are
+/// recognized as well, so no line based splitting is required. The reader
+/// keeps [`ValidationStats`] of all recognized candidates, including the ones
+/// skipped because of an invalid check digit.
+///
+/// An error of the underlying reader is yielded once, after which the iterator
+/// ends.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::extract::UidReader;
+///
+/// let input = "CHE-109.322.551;ADM-100.002.005\n".as_bytes();
+/// let uids: Vec<String> = UidReader::new(input)
+///     .map(|uid| uid.unwrap().to_string())
+///     .collect();
+/// assert_eq!(uids, ["CHE-109.322.551", "ADM-100.002.005"]);
+/// ```
+#[derive(Debug)]
+pub struct UidReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+    prev: u8,
+    eof: bool,
+    stats: ValidationStats,
+}
+
+impl<R: Read> UidReader<R> {
+    /// Creates a new pull parser over the reader.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(CHUNK_SIZE + WINDOW),
+            pos: 0,
+            prev: b' ',
+            eof: false,
+            stats: ValidationStats::new(),
+        }
+    }
+
+    /// Returns the statistics of the candidates recognized so far. Duplicates
+    /// are not tracked by the reader.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::extract::UidReader;
+    ///
+    /// let mut reader = UidReader::new("CHE-109.322.551 CHE-109.322.552".as_bytes());
+    /// assert_eq!(reader.by_ref().count(), 1);
+    /// assert_eq!(reader.stats().valid(), 1);
+    /// assert_eq!(reader.stats().invalid(), 1);
+    /// ```
+    pub fn stats(&self) -> &ValidationStats {
+        &self.stats
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Drops the consumed part of the buffer and reads the next chunk.
+    fn fill(&mut self) -> io::Result<()> {
+        if self.pos > 0 {
+            self.prev = self.buf[self.pos - 1];
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+
+        let len = self.buf.len();
+        self.buf.resize(len + CHUNK_SIZE, 0);
+        let read = loop {
+            match self.inner.read(&mut self.buf[len..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => break res,
+            }
+        };
+        let n = read.inspect_err(|_| self.buf.truncate(len))?;
+        self.buf.truncate(len + n);
+        self.eof = n == 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for UidReader<R> {
+    type Item = io::Result<SwissUid>;
+
+    /// Scans for the next valid UID, reading more input as needed.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if !self.eof && self.buf.len() - self.pos < WINDOW {
+                if let Err(e) = self.fill() {
+                    self.eof = true;
+                    self.buf.clear();
+                    self.pos = 0;
+                    return Some(Err(e));
+                }
+                continue;
+            }
+            if self.pos >= self.buf.len() {
+                return None;
+            }
+
+            let prev = match self.pos {
+                0 => self.prev,
+                pos => self.buf[pos - 1],
+            };
+            match (!prev.is_ascii_alphanumeric())
+                .then(|| match_at(&self.buf[self.pos..]))
+                .flatten()
+            {
+                Some((len, res)) => {
+                    self.pos += len;
+                    self.stats.record(&res);
+                    if let Ok(uid) = res {
+                        return Some(Ok(uid));
+                    }
+                }
+                None => self.pos += 1,
+            }
+        }
+    }
+}
+
+impl<R: Read> FusedIterator for UidReader<R> {}
+
+/// Adapters for iterators over extracted UIDs, such as [`find_uids`] and
+/// [`UidReader`].
+pub trait UidIteratorExt: Iterator + Sized
+where
+    Self::Item: ExtractedUid,
+{
+    /// Skips UIDs which were already yielded before, keeping the order of their
+    /// first occurrence. Errors are always passed through.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::extract::{find_uids, UidIteratorExt};
+    ///
+    /// let text = "CHE-109.322.551, CHE 109 322 551, ADM-100.002.005";
+    /// assert_eq!(find_uids(text).unique().count(), 2);
+    /// ```
+    fn unique(self) -> Unique<Self> {
+        Unique {
+            inner: self,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Collects the UIDs deduplicated and sorted by their [`Ord`], which is
+    /// the lexicographic order of their canonical string representation and
+    /// the order expected by [`search`](crate::search).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::extract::{find_uids, UidIteratorExt};
+    ///
+    /// let text = "CHE-109.322.551, ADM-100.002.005, CHE-100.002.005, CHE-109.322.551";
+    /// let uids: Vec<String> = find_uids(text)
+    ///     .sorted_unique()
+    ///     .iter()
+    ///     .map(|uid| uid.to_string())
+    ///     .collect();
+    /// assert_eq!(uids, ["ADM-100.002.005", "CHE-100.002.005", "CHE-109.322.551"]);
+    /// ```
+    fn sorted_unique(self) -> Vec<SwissUid>
+    where
+        Self: Iterator<Item = SwissUid>,
+    {
+        let mut uids: Vec<SwissUid> = self.collect();
+        uids.sort_unstable();
+        uids.dedup();
+        uids
+    }
+}
+
+impl<I> UidIteratorExt for I
+where
+    I: Iterator,
+    I::Item: ExtractedUid,
+{
+}
+
+/// An item yielded by the extractors, either a UID or a read result.
+pub trait ExtractedUid {
+    /// Returns the UID of the item, if any.
+    fn uid(&self) -> Option<&SwissUid>;
+}
+
+impl ExtractedUid for SwissUid {
+    fn uid(&self) -> Option<&SwissUid> {
+        Some(self)
+    }
+}
+
+impl ExtractedUid for io::Result<SwissUid> {
+    fn uid(&self) -> Option<&SwissUid> {
+        self.as_ref().ok()
+    }
+}
+
+/// Iterator skipping duplicate UIDs, see [`UidIteratorExt::unique`].
+#[derive(Debug, Clone)]
+pub struct Unique<I> {
+    inner: I,
+    seen: HashSet<u64>,
+}
+
+impl<I> Iterator for Unique<I>
+where
+    I: Iterator,
+    I::Item: ExtractedUid,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.by_ref().find(|item| match item.uid() {
+            Some(uid) => self.seen.insert(uid.to_u64()),
+            None => true,
+        })
+    }
+}
+
+/// Tries to recognize a UID at the start of `bytes`.
+///
+/// Returns the length of the recognized candidate and its validation result.
+pub(crate) fn match_at(bytes: &[u8]) -> Option<(usize, Result<SwissUid, UidError>)> {
+    let pfx = bytes.get(..3)?;
+    if !pfx.eq_ignore_ascii_case(b"CHE") && !pfx.eq_ignore_ascii_case(b"ADM") {
+        return None;
+    }
+
+    let mut i = 3;
+    if matches!(bytes.get(i), Some(b'-' | b' ')) {
+        i += 1;
+    }
+    for group in 0..3 {
+        if group > 0 && matches!(bytes.get(i), Some(b'.' | b' ')) {
+            i += 1;
+        }
+        for _ in 0..3 {
+            if !bytes.get(i)?.is_ascii_digit() {
+                return None;
+            }
+            i += 1;
+        }
+    }
+    if bytes.get(i).is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+
+    // The candidate only consists of ASCII characters
+    let candidate = ::std::str::from_utf8(&bytes[..i]).ok()?;
+    Some((i, SwissUid::new(candidate)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::uid::{UidErrorKind, UidPrefix};
+
+    /// A reader returning at most one byte per call
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((&b, rest)), Some(out)) => {
+                    *out = b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    const TEXT: &str = "Firma AG, CHE-109.322.551 MWST; Amt ADM 100 002 005.\n\
+                        ungültig: CHE-109.322.552, zu lang: CHE-109.322.5511234, \
+                        kein Wort: XCHE-109.322.551, kompakt: che109322551";
+
+    fn expected() -> Vec<String> {
+        vec![
+            "CHE-109.322.551".to_owned(),
+            "ADM-100.002.005".to_owned(),
+            "CHE-109.322.551".to_owned(),
+        ]
+    }
+
+    #[test]
+    fn test_find_uids() {
+        let uids: Vec<String> = find_uids(TEXT).map(|uid| uid.to_string()).collect();
+        assert_eq!(uids, expected());
+    }
+
+    #[test]
+    fn test_reader_across_boundaries() {
+        let uids: Vec<String> = UidReader::new(Trickle(TEXT.as_bytes()))
+            .map(|uid| uid.unwrap().to_string())
+            .collect();
+        assert_eq!(uids, expected());
+    }
+
+    #[test]
+    fn test_reader_stats() {
+        let mut reader = UidReader::new(Trickle(TEXT.as_bytes()));
+        assert_eq!(reader.by_ref().count(), 3);
+        assert_eq!(reader.stats().valid(), 3);
+        assert_eq!(reader.stats().errors(UidErrorKind::MismatchedCheckDigit), 1);
+        assert_eq!(reader.stats().total(), 4);
+    }
+
+    #[test]
+    fn test_unique() {
+        let uids: Vec<String> = find_uids(TEXT)
+            .unique()
+            .map(|uid| uid.to_string())
+            .collect();
+        assert_eq!(uids, ["CHE-109.322.551", "ADM-100.002.005"]);
+
+        let uids: Vec<String> = UidReader::new(Trickle(TEXT.as_bytes()))
+            .unique()
+            .map(|uid| uid.unwrap().to_string())
+            .collect();
+        assert_eq!(uids, ["CHE-109.322.551", "ADM-100.002.005"]);
+    }
+
+    #[test]
+    fn test_sorted_unique() {
+        let uids: Vec<String> = find_uids(TEXT)
+            .sorted_unique()
+            .iter()
+            .map(|uid| uid.to_string())
+            .collect();
+        assert_eq!(uids, ["ADM-100.002.005", "CHE-109.322.551"]);
+
+        let uids = SwissUid::iter_all(UidPrefix::CHE)
+            .step_by(7919)
+            .take(100)
+            .chain(SwissUid::iter_all(UidPrefix::ADM).take(10))
+            .chain(SwissUid::iter_all(UidPrefix::ADM).take(10))
+            .sorted_unique();
+        assert_eq!(uids.len(), 110);
+        assert!(crate::search::is_sorted(&uids));
+    }
+
+    #[test]
+    fn test_reader_large_input() {
+        let text = "CHE-109.322.551 ".repeat(2000);
+        assert_eq!(UidReader::new(text.as_bytes()).count(), 2000);
+    }
+
+    #[test]
+    fn test_reader_propagates_errors() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+        }
+        let mut reader = UidReader::new(Failing);
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        assert_eq!(UidReader::new(Failing).count(), 1);
+    }
+}
@@ -0,0 +1,181 @@
+//! Form field validation with localized error messages.
+//!
+//! Intended for frontends (e.g. Leptos or Yew compiled to WASM) which need a
+//! ready-to-display message in the language of the user.
+
+use crate::{
+    parse::ParseOptions,
+    uid::{SwissUid, UidError},
+};
+
+/// The languages error messages are available in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    De,
+    Fr,
+    It,
+    En,
+}
+
+impl Language {
+    /// Returns the language of a language tag such as `fr` or `fr-CH`, falling
+    /// back to German for unknown tags.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::form::Language;
+    ///
+    /// assert_eq!(Language::from_tag("fr-CH"), Language::Fr);
+    /// assert_eq!(Language::from_tag("rm"), Language::De);
+    /// ```
+    pub fn from_tag(tag: &str) -> Self {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        match primary.to_ascii_lowercase().as_str() {
+            "fr" => Language::Fr,
+            "it" => Language::It,
+            "en" => Language::En,
+            _ => Language::De,
+        }
+    }
+}
+
+impl UidError {
+    /// Returns a short message for end users in the given language.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::form::Language;
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let err = SwissUid::new("CHE-109.322.552").unwrap_err();
+    /// assert_eq!(err.localized_message(Language::Fr), "Le chiffre de contrôle n'est pas valide.");
+    /// ```
+    pub fn localized_message(&self, lang: Language) -> &'static str {
+        match (self, lang) {
+            (UidError::InvalidFormat(_, _), Language::De) => {
+                "Ungültiges Format, erwartet wird z.B. CHE-123.456.788."
+            }
+            (UidError::InvalidFormat(_, _), Language::Fr) => {
+                "Format invalide, p. ex. CHE-123.456.788 attendu."
+            }
+            (UidError::InvalidFormat(_, _), Language::It) => {
+                "Formato non valido, atteso p. es. CHE-123.456.788."
+            }
+            (UidError::InvalidFormat(_, _), Language::En) => {
+                "Invalid format, expected e.g. CHE-123.456.788."
+            }
+            (UidError::LeadingZeroNotAllowed(_), Language::De) => {
+                "Die Nummer darf nicht mit 0 beginnen."
+            }
+            (UidError::LeadingZeroNotAllowed(_), Language::Fr) => {
+                "Le numéro ne doit pas commencer par 0."
+            }
+            (UidError::LeadingZeroNotAllowed(_), Language::It) => {
+                "Il numero non può iniziare con 0."
+            }
+            (UidError::LeadingZeroNotAllowed(_), Language::En) => {
+                "The number must not start with 0."
+            }
+            (
+                UidError::InvalidCheckDigit(_, _) | UidError::MismatchedCheckDigit(_, _),
+                Language::De,
+            ) => "Die Prüfziffer ist ungültig.",
+            (
+                UidError::InvalidCheckDigit(_, _) | UidError::MismatchedCheckDigit(_, _),
+                Language::Fr,
+            ) => "Le chiffre de contrôle n'est pas valide.",
+            (
+                UidError::InvalidCheckDigit(_, _) | UidError::MismatchedCheckDigit(_, _),
+                Language::It,
+            ) => "La cifra di controllo non è valida.",
+            (
+                UidError::InvalidCheckDigit(_, _) | UidError::MismatchedCheckDigit(_, _),
+                Language::En,
+            ) => "The check digit is invalid.",
+        }
+    }
+}
+
+/// Validates the value of a form field and returns a localized error message
+/// suitable for displaying next to the field.
+///
+/// The French and Italian prefixes `IDE` and `IDI` are accepted, since users
+/// type the UID the way it is written in their language.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::form::{validate_field, Language};
+///
+/// assert!(validate_field(" CHE-109.322.551 ", Language::De).is_ok());
+/// assert!(validate_field("IDE-109.322.551", Language::Fr).is_ok());
+/// assert_eq!(validate_field("", Language::It), Err("Inserire un numero IDI."));
+/// ```
+pub fn validate_field(value: &str, lang: Language) -> Result<SwissUid, &'static str> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(match lang {
+            Language::De => "Bitte eine UID eingeben.",
+            Language::Fr => "Veuillez saisir un numéro IDE.",
+            Language::It => "Inserire un numero IDI.",
+            Language::En => "Please enter a UID.",
+        });
+    }
+    let options = ParseOptions {
+        allow_prefix_aliases: true,
+        ..Default::default()
+    };
+    SwissUid::parse_with(value, &options).map_err(|e| e.localized_message(lang))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_field() {
+        assert_eq!(
+            validate_field("CHE-109.322.551", Language::En).map(|uid| uid.to_string()),
+            Ok("CHE-109.322.551".to_owned())
+        );
+        assert_eq!(
+            validate_field("CHE-010.322.557", Language::De),
+            Err("Die Nummer darf nicht mit 0 beginnen.")
+        );
+        assert_eq!(
+            validate_field("ABC-109.322.551", Language::En),
+            Err("Invalid format, expected e.g. CHE-123.456.788.")
+        );
+        assert_eq!(
+            validate_field("CHE-100.000.160", Language::It),
+            Err("La cifra di controllo non è valida.")
+        );
+        assert_eq!(
+            validate_field("  ", Language::Fr),
+            Err("Veuillez saisir un numéro IDE.")
+        );
+    }
+
+    #[test]
+    fn test_validate_field_with_prefix_alias() {
+        let uid = SwissUid::new("CHE-109.322.551").unwrap();
+        assert_eq!(validate_field("IDE-109.322.551", Language::Fr), Ok(uid));
+        assert_eq!(validate_field("IDE-109.322.551", Language::It), Ok(uid));
+        assert_eq!(validate_field("IDI-109.322.551", Language::It), Ok(uid));
+        assert_eq!(
+            validate_field("IDE-109.322.552", Language::Fr),
+            Err("Le chiffre de contrôle n'est pas valide.")
+        );
+    }
+
+    #[test]
+    fn test_language_from_tag() {
+        assert_eq!(Language::from_tag("de-CH"), Language::De);
+        assert_eq!(Language::from_tag("IT_ch"), Language::It);
+        assert_eq!(Language::from_tag("en"), Language::En);
+        assert_eq!(Language::from_tag(""), Language::De);
+    }
+}
@@ -0,0 +1,352 @@
+//! Alternative output formats of UIDs.
+
+use crate::{
+    buf::SwissUidBuf,
+    uid::{SwissUid, CANONICAL_DIGIT_POS},
+};
+
+/// Separator characters used when formatting a UID with
+/// [`SwissUid::to_string_with`].
+///
+/// Parsing accepts any separators, so UIDs formatted with custom separators
+/// can be read back with [`SwissUid::new`].
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::format::Separators;
+/// use swiss_uid::uid::SwissUid;
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(uid.to_string_with(Separators::SPACES), "CHE 109 322 551");
+/// assert_eq!(SwissUid::new("CHE 109 322 551").unwrap(), uid);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Separators {
+    /// Separator between the prefix and the digits, `-` in the canonical form.
+    pub prefix: char,
+    /// Separator between the groups of three digits, `.` in the canonical form.
+    pub group: char,
+}
+
+impl Separators {
+    /// The separators of the canonical form `CHE-109.322.551`.
+    pub const CANONICAL: Self = Self {
+        prefix: '-',
+        group: '.',
+    };
+
+    /// Spaces only, `CHE 109 322 551`.
+    pub const SPACES: Self = Self {
+        prefix: ' ',
+        group: ' ',
+    };
+}
+
+impl Default for Separators {
+    fn default() -> Self {
+        Self::CANONICAL
+    }
+}
+
+/// Predefined formats of a UID, used with [`SwissUid::format`].
+///
+/// The compact form is also available through the alternate `Display`
+/// format `{:#}`.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::format::DisplayStyle;
+/// use swiss_uid::uid::SwissUid;
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(uid.format(DisplayStyle::Dotted), "CHE-109.322.551");
+/// assert_eq!(uid.format(DisplayStyle::Compact), "CHE109322551");
+/// assert_eq!(uid.format(DisplayStyle::Spaced), "CHE 109 322 551");
+/// assert_eq!(uid.format(DisplayStyle::NoPrefix), "109.322.551");
+/// assert_eq!(format!("{:#}", uid), "CHE109322551");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DisplayStyle {
+    /// The canonical form `CHE-109.322.551`.
+    #[default]
+    Dotted,
+    /// Prefix and digits without separators, `CHE109322551`.
+    Compact,
+    /// Prefix and digit groups separated by spaces, `CHE 109 322 551`.
+    Spaced,
+    /// The digit groups of the canonical form without the prefix, `109.322.551`.
+    NoPrefix,
+}
+
+impl SwissUid {
+    /// Returns the UID formatted in the given style.
+    pub fn format(&self, style: DisplayStyle) -> String {
+        let mut out = Vec::new();
+        self.write_styled(style, &mut out);
+        String::from_utf8(out).expect("formatted UIDs only consist of ASCII characters")
+    }
+
+    /// Appends the UID formatted in the given style to `out`, derived from the
+    /// canonical layout of [`SwissUidBuf`].
+    fn write_styled(&self, style: DisplayStyle, out: &mut Vec<u8>) {
+        let buf = SwissUidBuf::new(*self);
+        let canonical = buf.as_bytes();
+        match style {
+            DisplayStyle::Dotted => out.extend_from_slice(canonical),
+            DisplayStyle::Compact => {
+                out.extend_from_slice(&canonical[..CANONICAL_DIGIT_POS[0] - 1]);
+                out.extend(CANONICAL_DIGIT_POS.iter().map(|&i| canonical[i]));
+            }
+            DisplayStyle::Spaced => {
+                out.extend(canonical.iter().map(
+                    |&b| {
+                        if b.is_ascii_alphanumeric() {
+                            b
+                        } else {
+                            b' '
+                        }
+                    },
+                ))
+            }
+            DisplayStyle::NoPrefix => out.extend_from_slice(&canonical[CANONICAL_DIGIT_POS[0]..]),
+        }
+    }
+
+    /// Returns the UID formatted with the given separators.
+    pub fn to_string_with(&self, separators: Separators) -> String {
+        SwissUidBuf::new(*self)
+            .chars()
+            .map(|c| match c {
+                '-' => separators.prefix,
+                '.' => separators.group,
+                c => c,
+            })
+            .collect()
+    }
+
+    /// Returns the canonical representation padded with `pad` or truncated to
+    /// exactly `width` bytes, as required by fixed record layouts.
+    ///
+    /// Returns `None` if `pad` is not an ASCII character, since the result
+    /// would not be `width` bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.format_fixed(20, ' ').unwrap(), "CHE-109.322.551     ");
+    /// assert_eq!(uid.format_fixed(12, ' ').unwrap(), "CHE-109.322.");
+    /// assert_eq!(uid.format_fixed(20, 'ü'), None);
+    /// ```
+    pub fn format_fixed(&self, width: usize, pad: char) -> Option<String> {
+        if !pad.is_ascii() {
+            return None;
+        }
+        let mut out = SwissUidBuf::new(*self).as_str().to_owned();
+        out.truncate(width);
+        out.extend(::std::iter::repeat_n(pad, width - out.len()));
+        Some(out)
+    }
+
+    /// Returns the canonical representation with all digits except the first
+    /// and the check digit replaced by `•`, for logs which must not contain
+    /// the full identifier.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.masked(), "CHE-1••.•••.••1");
+    /// ```
+    pub fn masked(&self) -> String {
+        self.masked_with(1, 1)
+    }
+
+    /// Returns the canonical representation with the digits replaced by `•`,
+    /// except for the first `leading` and the last `trailing` of the 9 digits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.masked_with(0, 3), "CHE-•••.•••.551");
+    /// ```
+    pub fn masked_with(&self, leading: usize, trailing: usize) -> String {
+        let buf = SwissUidBuf::new(*self);
+        let mut out = String::with_capacity(buf.len() + 2 * CANONICAL_DIGIT_POS.len());
+        out.push_str(&buf[..CANONICAL_DIGIT_POS[0]]);
+        let mut k = 0;
+        for c in buf[CANONICAL_DIGIT_POS[0]..].chars() {
+            if !c.is_ascii_digit() {
+                out.push(c);
+                continue;
+            }
+            let visible = k < leading || k + trailing >= CANONICAL_DIGIT_POS.len();
+            out.push(if visible { c } else { '•' });
+            k += 1;
+        }
+        out
+    }
+
+    /// Returns a short token for places where the full 15 characters do not
+    /// fit, e.g. `C-2551` for `CHE-109.322.551` and `A-0029` for
+    /// `ADM-100.000.029`.
+    ///
+    /// The token consists of the first letter of the prefix and the last four
+    /// digits, including the check digit. It is stable but not unique: all
+    /// UIDs of a prefix share only 10'000 tokens. With randomly distributed
+    /// UIDs, a table of 15 rows has a collision with a probability of about
+    /// 1%, and a table of 120 rows with about 50%. Tokens should therefore
+    /// only be used as labels next to data which identifies the UID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.short_token(), "C-2551");
+    /// ```
+    pub fn short_token(&self) -> String {
+        format!(
+            "{}-{:04}",
+            &self.category_code()[..1],
+            self.organisation_id() % 10_000
+        )
+    }
+}
+
+/// Writes the UIDs formatted in the given style into one contiguous buffer and
+/// returns the offsets of the strings within `out`.
+///
+/// The offsets are in the layout used by columnar formats: there is one more
+/// offset than UIDs, the `i`th UID is at `out[offsets[i]..offsets[i + 1]]`.
+/// Existing content of `out` is kept.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::format::{format_many, DisplayStyle};
+/// use swiss_uid::uid::SwissUid;
+///
+/// let uids = [
+///     SwissUid::new("CHE-109.322.551").unwrap(),
+///     SwissUid::new("ADM-100.000.029").unwrap(),
+/// ];
+/// let mut out = Vec::new();
+/// let offsets = format_many(&uids, DisplayStyle::Dotted, &mut out);
+/// assert_eq!(offsets, [0, 15, 30]);
+/// assert_eq!(&out[offsets[1]..offsets[2]], b"ADM-100.000.029");
+///
+/// let offsets = format_many(&uids, DisplayStyle::Compact, &mut out);
+/// assert_eq!(&out[offsets[0]..offsets[1]], b"CHE109322551");
+/// ```
+pub fn format_many(uids: &[SwissUid], style: DisplayStyle, out: &mut Vec<u8>) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(uids.len() + 1);
+    offsets.push(out.len());
+    for uid in uids {
+        uid.write_styled(style, out);
+        if offsets.len() == 1 {
+            // All UIDs have the same length in a style
+            out.reserve((uids.len() - 1) * (out.len() - offsets[0]));
+        }
+        offsets.push(out.len());
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::uid::UidPrefix;
+
+    #[test]
+    fn test_to_string_with() {
+        let uid = SwissUid::new("ADM-100.000.029").unwrap();
+        assert_eq!(uid.to_string_with(Separators::default()), uid.to_string());
+        assert_eq!(uid.to_string_with(Separators::SPACES), "ADM 100 000 029");
+
+        let custom = Separators {
+            prefix: '_',
+            group: '\'',
+        };
+        let formatted = uid.to_string_with(custom);
+        assert_eq!(formatted, "ADM_100'000'029");
+        assert_eq!(SwissUid::new(&formatted).unwrap(), uid);
+    }
+
+    #[test]
+    fn test_format_styles() {
+        let uid = SwissUid::new("ADM-100.000.029").unwrap();
+        assert_eq!(uid.format(DisplayStyle::default()), uid.to_string());
+        assert_eq!(uid.format(DisplayStyle::Compact), "ADM100000029");
+        assert_eq!(format!("{:#}", uid), "ADM100000029");
+        assert_eq!(uid.format(DisplayStyle::Spaced), "ADM 100 000 029");
+        assert_eq!(uid.format(DisplayStyle::NoPrefix), "100.000.029");
+        for style in [
+            DisplayStyle::Dotted,
+            DisplayStyle::Compact,
+            DisplayStyle::Spaced,
+        ] {
+            assert_eq!(SwissUid::new(&uid.format(style)).unwrap(), uid);
+        }
+    }
+
+    #[test]
+    fn test_format_fixed() {
+        let uid = SwissUid::new("ADM-100.000.029").unwrap();
+        assert_eq!(uid.format_fixed(16, '*').unwrap(), "ADM-100.000.029*");
+        assert_eq!(uid.format_fixed(15, '*').unwrap(), "ADM-100.000.029");
+        assert_eq!(uid.format_fixed(4, '*').unwrap(), "ADM-");
+        assert_eq!(uid.format_fixed(0, '*').unwrap(), "");
+        assert_eq!(uid.format_fixed(20, '0').unwrap().len(), 20);
+        assert_eq!(uid.format_fixed(20, 'ü'), None);
+        assert_eq!(uid.format_fixed(4, '\u{fc}'), None);
+    }
+
+    #[test]
+    fn test_short_token() {
+        let uid = SwissUid::new("ADM-100.000.029").unwrap();
+        assert_eq!(uid.short_token(), "A-0029");
+        let uid = SwissUid::new("CHE-100.000.006").unwrap();
+        assert_eq!(uid.short_token(), "C-0006");
+    }
+
+    #[test]
+    fn test_masked_with() {
+        let uid = SwissUid::new("ADM-100.000.029").unwrap();
+        assert_eq!(uid.masked_with(0, 0), "ADM-•••.•••.•••");
+        assert_eq!(uid.masked_with(9, 0), "ADM-100.000.029");
+        assert_eq!(uid.masked_with(5, 5), "ADM-100.000.029");
+        assert_eq!(uid.masked_with(4, 1), "ADM-100.0••.••9");
+    }
+
+    #[test]
+    fn test_format_many() {
+        let uids: Vec<SwissUid> = SwissUid::iter_all(UidPrefix::ADM).take(50).collect();
+        for style in [
+            DisplayStyle::Dotted,
+            DisplayStyle::Compact,
+            DisplayStyle::Spaced,
+            DisplayStyle::NoPrefix,
+        ] {
+            let mut out = b"header\n".to_vec();
+            let offsets = format_many(&uids, style, &mut out);
+            assert_eq!(offsets.len(), uids.len() + 1);
+            assert_eq!(offsets[0], 7);
+            for (uid, w) in uids.iter().zip(offsets.windows(2)) {
+                let formatted = ::std::str::from_utf8(&out[w[0]..w[1]]).unwrap();
+                assert_eq!(formatted, uid.format(style));
+            }
+        }
+        assert_eq!(format_many(&[], DisplayStyle::Dotted, &mut Vec::new()), [0]);
+    }
+}
@@ -0,0 +1,138 @@
+//! Generation of synthetic UIDs following a configurable distribution.
+
+use ::std::ops::RangeInclusive;
+
+use ::rand::distributions::{Distribution, Uniform, WeightedIndex};
+
+use crate::uid::{SwissUid, UidError, UidPrefix};
+
+/// A distribution over the 8 main digits (read as a number) composed of
+/// weighted blocks, each sampled uniformly.
+///
+/// This allows synthetic data to mimic the skew of real allocations, e.g. most
+/// UIDs in the `1xx.xxx.xx` block and only some in the `4xx.xxx.xx` block.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::generate::WeightedBlocks;
+/// use swiss_uid::uid::SwissUid;
+///
+/// let blocks = WeightedBlocks::new([
+///     (10_000_000..=19_999_999, 0.9),
+///     (40_000_000..=49_999_999, 0.1),
+/// ])
+/// .unwrap();
+/// let uid = SwissUid::rand_with_distribution(&blocks).unwrap();
+/// assert!(uid.to_string().starts_with("CHE-1") || uid.to_string().starts_with("CHE-4"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightedBlocks {
+    blocks: Vec<Uniform<u32>>,
+    weights: WeightedIndex<f64>,
+}
+
+impl WeightedBlocks {
+    /// Creates the distribution from blocks of main numbers and their weights.
+    ///
+    /// All blocks must lie within `10_000_000..=99_999_999`, the weights must be
+    /// non-negative and not all zero.
+    pub fn new<I>(blocks: I) -> Result<Self, UidError>
+    where
+        I: IntoIterator<Item = (RangeInclusive<u32>, f64)>,
+    {
+        let (ranges, weights): (Vec<_>, Vec<_>) = blocks.into_iter().unzip();
+        if ranges
+            .iter()
+            .any(|r| r.is_empty() || *r.start() < 10_000_000 || *r.end() > 99_999_999)
+        {
+            return Err(UidError::InvalidFormat(
+                "Blocks must lie within 10000000..=99999999",
+                0,
+            ));
+        }
+
+        let weights = WeightedIndex::new(weights).map_err(|_| {
+            UidError::InvalidFormat("Block weights must be non-negative and not all zero", 0)
+        })?;
+        Ok(Self {
+            blocks: ranges.into_iter().map(Uniform::from).collect(),
+            weights,
+        })
+    }
+}
+
+impl Distribution<u32> for WeightedBlocks {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+        self.blocks[self.weights.sample(rng)].sample(rng)
+    }
+}
+
+impl SwissUid {
+    /// Generates a random valid Swiss UID whose 8 main digits, read as a number,
+    /// are sampled from `dist`.
+    ///
+    /// Samples whose check digit would be 10 are rejected and drawn again, which
+    /// slightly reweights the distribution towards valid numbers. An error is
+    /// returned if a sample lies outside of `10_000_000..=99_999_999` or no valid
+    /// UID is found after a bounded number of attempts.
+    pub fn rand_with_distribution<D>(dist: &D) -> Result<Self, UidError>
+    where
+        D: Distribution<u32> + ?Sized,
+    {
+        const MAX_ATTEMPTS: usize = 64;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..MAX_ATTEMPTS {
+            let n = dist.sample(&mut rng);
+            if !(10_000_000..=99_999_999).contains(&n) {
+                return Err(UidError::InvalidFormat(
+                    "Sampled number must lie within 10000000..=99999999",
+                    0,
+                ));
+            }
+            if let Ok(uid) = Self::from_main_number(UidPrefix::CHE, n) {
+                return Ok(uid);
+            }
+        }
+        Err(UidError::InvalidCheckDigit(10, 8))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_weighted_blocks() {
+        let blocks = WeightedBlocks::new([
+            (10_000_000..=10_999_999, 1.0),
+            (20_000_000..=20_999_999, 0.0),
+        ])
+        .unwrap();
+        for _ in 0..100 {
+            let uid = SwissUid::rand_with_distribution(&blocks).unwrap();
+            assert!(uid.to_string().starts_with("CHE-10"), "{}", uid);
+        }
+    }
+
+    #[test]
+    fn test_weighted_blocks_invalid() {
+        assert!(WeightedBlocks::new([(0..=10_000_000, 1.0)]).is_err());
+        assert!(WeightedBlocks::new([(10_000_000..=19_999_999, 0.0)]).is_err());
+        assert!(WeightedBlocks::new([(10_000_000..=19_999_999, -1.0)]).is_err());
+        assert!(WeightedBlocks::new(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_rand_with_distribution_out_of_range() {
+        let dist = Uniform::new_inclusive(0, 9_999_999);
+        assert_eq!(
+            SwissUid::rand_with_distribution(&dist),
+            Err(UidError::InvalidFormat(
+                "Sampled number must lie within 10000000..=99999999",
+                0
+            ))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Interning of UIDs into small integer handles.
+
+use ::std::collections::HashMap;
+
+use crate::uid::SwissUid;
+
+/// Maps UIDs to dense `u32` handles and back.
+///
+/// Handles are assigned in insertion order starting at 0. Large relation
+/// datasets can then store a `u32` per reference instead of the full UID.
+/// There are less valid UIDs than `u32::MAX`, so handles never overflow.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::intern::UidInterner;
+/// use swiss_uid::uid::SwissUid;
+///
+/// let mut interner = UidInterner::new();
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// let handle = interner.intern(uid);
+/// assert_eq!(interner.intern(uid), handle);
+/// assert_eq!(interner.resolve(handle), Some(uid));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UidInterner {
+    handles: HashMap<u64, u32>,
+    uids: Vec<SwissUid>,
+}
+
+impl UidInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty interner with space for at least `capacity` UIDs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            handles: HashMap::with_capacity(capacity),
+            uids: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the handle of the UID, assigning a new one if it was not
+    /// interned yet.
+    pub fn intern(&mut self, uid: SwissUid) -> u32 {
+        let next = self.uids.len() as u32;
+        let handle = *self.handles.entry(uid.to_u64()).or_insert(next);
+        if handle == next {
+            self.uids.push(uid);
+        }
+        handle
+    }
+
+    /// Returns the handle of the UID if it was interned.
+    pub fn get(&self, uid: &SwissUid) -> Option<u32> {
+        self.handles.get(&uid.to_u64()).copied()
+    }
+
+    /// Returns the UID of the handle.
+    pub fn resolve(&self, handle: u32) -> Option<SwissUid> {
+        self.uids.get(handle as usize).copied()
+    }
+
+    /// Returns the number of interned UIDs.
+    pub fn len(&self) -> usize {
+        self.uids.len()
+    }
+
+    /// Returns `true` if no UID was interned.
+    pub fn is_empty(&self) -> bool {
+        self.uids.is_empty()
+    }
+
+    /// Returns an iterator over all handles and their UIDs in handle order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, SwissUid)> + '_ {
+        self.uids
+            .iter()
+            .enumerate()
+            .map(|(i, &uid)| (i as u32, uid))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intern_and_resolve() {
+        let uid1 = SwissUid::new("CHE-109.322.551").unwrap();
+        let uid2 = SwissUid::new("ADM-109.322.551").unwrap();
+        let uid3 = SwissUid::new("CHE-100.002.005").unwrap();
+
+        let mut interner = UidInterner::with_capacity(3);
+        assert!(interner.is_empty());
+        assert_eq!(interner.intern(uid1), 0);
+        assert_eq!(interner.intern(uid2), 1);
+        assert_eq!(interner.intern(uid1), 0);
+        assert_eq!(interner.get(&uid2), Some(1));
+        assert_eq!(interner.get(&uid3), None);
+        assert_eq!(interner.len(), 2);
+
+        assert_eq!(interner.resolve(1), Some(uid2));
+        assert_eq!(interner.resolve(2), None);
+        assert_eq!(interner.iter().collect::<Vec<_>>(), [(0, uid1), (1, uid2)]);
+    }
+}
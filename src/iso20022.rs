@@ -0,0 +1,61 @@
+//! Helpers for the UID as party identification in ISO 20022 messages.
+//!
+//! Payment messages such as `pain.001` or `camt.053` identify an organisation
+//! with an `OrgId/Othr` block consisting of the identifier and the scheme it
+//! belongs to. The UID is the Swiss tax identification number, so it uses the
+//! external code `TXID`.
+
+use crate::uid::SwissUid;
+
+/// Code of the `ExternalOrganisationIdentification1Code` list used for the UID.
+pub const SCHEME_CODE: &str = "TXID";
+
+/// Returns the `Othr` element identifying the organisation by its UID.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{iso20022, uid::SwissUid};
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(
+///     iso20022::to_othr_xml(&uid),
+///     "<Othr><Id>CHE-109.322.551</Id><SchmeNm><Cd>TXID</Cd></SchmeNm></Othr>"
+/// );
+/// ```
+pub fn to_othr_xml(uid: &SwissUid) -> String {
+    format!(
+        "<Othr><Id>{}</Id><SchmeNm><Cd>{}</Cd></SchmeNm></Othr>",
+        uid, SCHEME_CODE
+    )
+}
+
+/// Returns the complete `OrgId` element, ready to be embedded in the `Id`
+/// element of a party.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{iso20022, uid::SwissUid};
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// let party = format!("<Cdtr><Nm>Muster AG</Nm><Id>{}</Id></Cdtr>", iso20022::to_org_id_xml(&uid));
+/// assert!(party.contains("<OrgId><Othr><Id>CHE-109.322.551</Id>"));
+/// ```
+pub fn to_org_id_xml(uid: &SwissUid) -> String {
+    format!("<OrgId>{}</OrgId>", to_othr_xml(uid))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_org_id_xml() {
+        let uid = SwissUid::new("adm 100 000 029").unwrap();
+        assert_eq!(
+            to_org_id_xml(&uid),
+            "<OrgId><Othr><Id>ADM-100.000.029</Id><SchmeNm><Cd>TXID</Cd></SchmeNm></Othr></OrgId>"
+        );
+    }
+}
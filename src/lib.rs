@@ -1,2 +1,23 @@
+pub mod buf;
+mod diagnostic;
+pub mod edifact;
+pub mod explain;
+pub mod extract;
+pub mod form;
+pub mod format;
+#[cfg(feature = "rand")]
+pub mod generate;
+pub mod intern;
+pub mod iso20022;
+mod macros;
+pub mod manual;
+pub mod nibble;
+pub mod parse;
+pub mod qrbill;
+pub mod search;
+pub mod sql;
+pub mod stats;
+pub mod test_vectors;
 pub mod uid;
-pub(crate) mod utils;
+
+pub use uid::{SwissUid, UidError, UidErrorKind, UidPrefix};
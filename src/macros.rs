@@ -0,0 +1,33 @@
+/// Creates a [`SwissUid`](crate::uid::SwissUid) from a string literal which is
+/// validated at compile time.
+///
+/// The macro can be used in `const` items as well as in expressions. Invalid
+/// UIDs fail the compilation.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{uid, uid::SwissUid};
+///
+/// const FIXTURE: SwissUid = uid!("CHE-109.322.551");
+/// assert_eq!(FIXTURE.to_string(), "CHE-109.322.551");
+/// assert_eq!(uid!("adm 100 000 029").to_string(), "ADM-100.000.029");
+/// ```
+///
+/// A wrong check digit is a compile error:
+///
+/// ```rust,compile_fail
+/// let uid = swiss_uid::uid!("CHE-109.322.552");
+/// ```
+#[macro_export]
+macro_rules! uid {
+    ($uid:literal) => {{
+        const UID: $crate::uid::SwissUid = match $crate::uid::SwissUid::parse_const($uid) {
+            ::core::result::Result::Ok(uid) => uid,
+            ::core::result::Result::Err(_) => {
+                ::core::panic!(::core::concat!("invalid Swiss UID: ", $uid))
+            }
+        };
+        UID
+    }};
+}
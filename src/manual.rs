@@ -0,0 +1,214 @@
+//! Validation of UIDs typed in by hand, e.g. dictated over the phone.
+//!
+//! The check digit catches every single wrong digit and every swap of two
+//! adjacent digits. For such input [`validate_manual_entry`] additionally
+//! lists the valid UIDs the input most likely was meant to be.
+
+use ::std::{error::Error, fmt};
+
+use crate::{
+    parse::sanitize_input,
+    uid::{SwissUid, UidError, UidPrefix},
+};
+
+/// A change which turns the erroneous input into a valid UID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Correction {
+    /// The digits at `position` and `position + 1` were swapped.
+    Transposition { position: usize },
+    /// The digit at `position` was `found` instead of `expected`.
+    Substitution {
+        position: usize,
+        found: u8,
+        expected: u8,
+    },
+}
+
+/// A valid UID differing from the input by a single [`Correction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidate {
+    /// The corrected UID.
+    pub uid: SwissUid,
+    /// The correction applied to the input, positions count the 9 digits
+    /// starting at 0.
+    pub correction: Correction,
+}
+
+/// Error of [`validate_manual_entry`] with the candidates for the intended UID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManualEntryError {
+    error: UidError,
+    candidates: Vec<Candidate>,
+}
+
+impl ManualEntryError {
+    /// Returns the error the input was rejected with.
+    pub fn error(&self) -> UidError {
+        self.error
+    }
+
+    /// Returns the candidates, transpositions first since they are the more
+    /// likely typing error.
+    pub fn candidates(&self) -> &[Candidate] {
+        &self.candidates
+    }
+}
+
+impl fmt::Display for ManualEntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} candidates)", self.error, self.candidates.len())
+    }
+}
+
+impl Error for ManualEntryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Validates a UID and, if it is invalid, searches for the valid UIDs which
+/// differ by one swap of adjacent digits or by one wrong digit.
+///
+/// Candidates are only searched if the input has a valid prefix and 9 digits.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::manual::{validate_manual_entry, Correction};
+///
+/// assert!(validate_manual_entry("CHE-109.322.551").is_ok());
+///
+/// let err = validate_manual_entry("CHE-190.322.551").unwrap_err();
+/// let first = err.candidates()[0];
+/// assert_eq!(first.uid.to_string(), "CHE-109.322.551");
+/// assert_eq!(first.correction, Correction::Transposition { position: 1 });
+/// ```
+pub fn validate_manual_entry(input: &str) -> Result<SwissUid, ManualEntryError> {
+    let error = match SwissUid::new(input) {
+        Ok(uid) => return Ok(uid),
+        Err(error) => error,
+    };
+    let candidates = entered_digits(input)
+        .map(|(pfx, digits)| find_candidates(pfx, digits))
+        .unwrap_or_default();
+    Err(ManualEntryError { error, candidates })
+}
+
+/// Returns the prefix and the 9 digits of the input, if it has exactly 9.
+///
+/// The input is sanitized like in [`SwissUid::new`] first.
+fn entered_digits(input: &str) -> Option<(UidPrefix, [u8; 9])> {
+    let input = &*sanitize_input(input);
+    let pfx = UidPrefix::from_canonical(input.get(..3)?).ok()?;
+    let mut digits = [0u8; 9];
+    let mut len = 0;
+    for d in input[3..].bytes().filter(u8::is_ascii_digit) {
+        *digits.get_mut(len)? = d - b'0';
+        len += 1;
+    }
+    (len == digits.len()).then_some((pfx, digits))
+}
+
+fn find_candidates(pfx: UidPrefix, digits: [u8; 9]) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    for position in 0..digits.len() - 1 {
+        if digits[position] == digits[position + 1] {
+            continue;
+        }
+        let mut swapped = digits;
+        swapped.swap(position, position + 1);
+        if let Ok(uid) = SwissUid::from_digit_iter(pfx, swapped) {
+            candidates.push(Candidate {
+                uid,
+                correction: Correction::Transposition { position },
+            });
+        }
+    }
+    for (position, &found) in digits.iter().enumerate() {
+        for expected in (0..=9).filter(|&d| d != found) {
+            let mut replaced = digits;
+            replaced[position] = expected;
+            if let Ok(uid) = SwissUid::from_digit_iter(pfx, replaced) {
+                candidates.push(Candidate {
+                    uid,
+                    correction: Correction::Substitution {
+                        position,
+                        found,
+                        expected,
+                    },
+                });
+            }
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_finds_every_transposition() {
+        let uid = SwissUid::new("CHE-109.322.551").unwrap();
+        let digits: Vec<u8> = uid.iter_digits().collect();
+        for position in 0..8 {
+            let mut swapped = digits.clone();
+            swapped.swap(position, position + 1);
+            if swapped == digits {
+                continue;
+            }
+            let input = format!(
+                "CHE{}",
+                swapped.iter().map(|d| d.to_string()).collect::<String>()
+            );
+            let err = validate_manual_entry(&input).unwrap_err();
+            assert!(
+                err.candidates().contains(&Candidate {
+                    uid,
+                    correction: Correction::Transposition { position },
+                }),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_substitution_candidates() {
+        let err = validate_manual_entry("CHE-109.322.552").unwrap_err();
+        assert_eq!(
+            err.error().kind(),
+            crate::uid::UidErrorKind::MismatchedCheckDigit
+        );
+        let expected = Candidate {
+            uid: SwissUid::new("CHE-109.322.551").unwrap(),
+            correction: Correction::Substitution {
+                position: 8,
+                found: 2,
+                expected: 1,
+            },
+        };
+        assert!(err.candidates().contains(&expected));
+        assert!(err
+            .candidates()
+            .iter()
+            .all(|c| c.uid.to_string().starts_with("CHE-")));
+    }
+
+    #[test]
+    fn test_candidates_for_pasted_input() {
+        let err = validate_manual_entry("\u{feff}CHE-190.322.551").unwrap_err();
+        assert_eq!(
+            err.candidates()[0].uid,
+            SwissUid::new("CHE-109.322.551").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_candidates_for_malformed_input() {
+        for input in ["CHE-109.322.55", "XYZ-109.322.552", "C"] {
+            let err = validate_manual_entry(input).unwrap_err();
+            assert!(err.candidates().is_empty(), "{}", input);
+        }
+    }
+}
@@ -1,10 +1,30 @@
-use ::std::ops::{BitAnd, BitOr, Shl, Shr};
+//! Helpers for numbers storing one decimal digit per nibble (4 bits).
+//!
+//! The digits `1, 2, 3, 4` are stored as `0x1234`, which keeps every digit
+//! readable in a hexadecimal dump. The traits are implemented for `u16`, `u32`
+//! and `u64`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use swiss_uid::nibble::{IntoNibbles, IntoNibblesNum};
+//!
+//! let packed: u32 = [1u8, 0, 9, 3, 2, 2, 5, 5].into_nibbles_num();
+//! assert_eq!(packed, 0x1093_2255);
+//!
+//! let digits: Vec<u8> = packed.into_iter_nibbles().collect();
+//! assert_eq!(digits, [1, 0, 9, 3, 2, 2, 5, 5]);
+//! ```
 
-use ::num::cast::AsPrimitive;
+use ::std::ops::{BitAnd, BitOr, Shl, Shr};
 
+/// Numbers which can be built from a slice of nibbles.
 pub trait FromNibbles:
     Shl<usize, Output = Self> + Default + BitOr<Output = Self> + From<u8>
 {
+    /// Packs the nibbles into a number, the first nibble becoming the most
+    /// significant one. Nibbles exceeding the capacity of the number are
+    /// ignored.
     fn from_nibbles(digits: &[u8]) -> Self {
         digits
             .iter()
@@ -15,11 +35,14 @@ pub trait FromNibbles:
 
 impl FromNibbles for u16 {}
 impl FromNibbles for u32 {}
+impl FromNibbles for u64 {}
 
+/// Conversion of a slice of nibbles into a number, see [`FromNibbles`].
 pub trait IntoNibblesNum<T>
 where
     T: FromNibbles,
 {
+    #[allow(clippy::wrong_self_convention)]
     fn into_nibbles_num(&self) -> T;
 }
 
@@ -32,8 +55,9 @@ where
     }
 }
 
+/// Numbers which can be split into their nibbles.
 pub trait IntoNibbles:
-    FromNibbles + Shr<usize, Output = Self> + BitAnd<Output = Self> + AsPrimitive<u8>
+    FromNibbles + Copy + Shr<usize, Output = Self> + BitAnd<Output = Self> + TryInto<u8>
 {
     /// Returns an iterator over the nibbles (4-bit digits) of the number.
     /// The iterator starts with the most significant nibble.
@@ -41,14 +65,14 @@ pub trait IntoNibbles:
     fn into_iter_nibbles(self) -> impl Iterator<Item = u8> {
         let n = self;
         (0..(size_of::<Self>() * 2))
-            .into_iter()
             .rev()
-            .map(move |i| (n >> (i * 4)).as_() & 0x0f)
+            .map(move |i| ((n >> (i * 4)) & Self::from(0x0f)).try_into().unwrap_or(0))
     }
 }
 
 impl IntoNibbles for u16 {}
 impl IntoNibbles for u32 {}
+impl IntoNibbles for u64 {}
 
 #[cfg(test)]
 mod tests {
@@ -75,4 +99,13 @@ mod tests {
         assert_eq!(n_quad, 0xbcde);
         assert_eq!(format!("{n_quad:#x}"), format!("{:#x}", 0xbcde));
     }
+
+    #[test]
+    fn test_u64_nibbles() {
+        let n = [7u8, 5, 6, 1, 2, 3, 4, 5, 6, 7, 8, 9, 7];
+        let packed: u64 = n.into_nibbles_num();
+        assert_eq!(packed, 0x0007_5612_3456_7897);
+        let split: Vec<u8> = packed.into_iter_nibbles().skip(3).collect();
+        assert_eq!(split, n);
+    }
 }
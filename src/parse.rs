@@ -0,0 +1,652 @@
+//! Configurable parsing of UIDs.
+
+use ::std::{borrow::Cow, fmt, str::FromStr};
+
+use crate::uid::{split_prefix, SwissUid, UidError, UidPrefix};
+
+/// Suffix of a UID registered for VAT (Mehrwertsteuer).
+pub const SUFFIX_MWST: &str = "MWST";
+/// French spelling of [`SUFFIX_MWST`] (taxe sur la valeur ajoutée).
+pub const SUFFIX_TVA: &str = "TVA";
+/// Italian spelling of [`SUFFIX_MWST`] (imposta sul valore aggiunto).
+pub const SUFFIX_IVA: &str = "IVA";
+/// English spelling of [`SUFFIX_MWST`].
+pub const SUFFIX_VAT: &str = "VAT";
+/// Suffix of a UID registered in the commercial register (Handelsregister).
+pub const SUFFIX_HR: &str = "HR";
+/// French and Italian spelling of [`SUFFIX_HR`] (registre du commerce,
+/// registro di commercio).
+pub const SUFFIX_RC: &str = "RC";
+
+/// All suffix spellings accepted after the UID even if trailing characters
+/// are rejected, e.g. `CHE-109.322.551 TVA`, each mapped to the German
+/// spelling used by [`SwissUid::to_string_mwst`] and [`SwissUid::to_string_hr`].
+///
+/// Suffixes are matched case-insensitively.
+pub const SUFFIX_ALIASES: [(&str, &str); 6] = [
+    (SUFFIX_MWST, SUFFIX_MWST),
+    (SUFFIX_TVA, SUFFIX_MWST),
+    (SUFFIX_IVA, SUFFIX_MWST),
+    (SUFFIX_VAT, SUFFIX_MWST),
+    (SUFFIX_HR, SUFFIX_HR),
+    (SUFFIX_RC, SUFFIX_HR),
+];
+
+/// Returns the input without invisible formatting characters, which are often
+/// copied along with a UID from e-mails or web pages: zero-width characters,
+/// byte order marks, soft hyphens and bidirectional controls.
+///
+/// The input is only copied if it contains such characters. The parsers call
+/// this function before parsing, so it is only needed to clean up the input
+/// for other purposes, e.g. storing it.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::parse::sanitize_input;
+///
+/// assert_eq!(sanitize_input("\u{feff}CHE-109.322.551\u{200b}"), "CHE-109.322.551");
+/// ```
+pub fn sanitize_input(input: &str) -> Cow<'_, str> {
+    if input.contains(is_invisible) {
+        Cow::Owned(input.chars().filter(|&c| !is_invisible(c)).collect())
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Moves the position of an error in the sanitized `input` to the index of
+/// the same character in `input`.
+pub(crate) fn restore_position(e: UidError, input: &str) -> UidError {
+    let position = input
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !is_invisible(*c))
+        .nth(e.position())
+        .map_or_else(|| input.chars().count(), |(i, _)| i);
+    e.at(position)
+}
+
+/// Returns `true` for the characters removed by [`sanitize_input`].
+pub(crate) fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00ad}'
+            | '\u{061c}'
+            | '\u{180e}'
+            | '\u{200b}'..='\u{200f}'
+            | '\u{202a}'..='\u{202e}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{feff}'
+    )
+}
+
+/// A register designation written after the UID, see [`SUFFIX_ALIASES`].
+///
+/// The variants are declared in the order of [`SUFFIX_ALIASES`], their
+/// discriminants index into the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UidSuffix {
+    /// Registered for VAT, German spelling.
+    MWST,
+    /// Registered for VAT, French spelling.
+    TVA,
+    /// Registered for VAT, Italian spelling.
+    IVA,
+    /// Registered for VAT, English spelling.
+    VAT,
+    /// Registered in the commercial register, German spelling.
+    HR,
+    /// Registered in the commercial register, French and Italian spelling.
+    RC,
+}
+
+impl UidSuffix {
+    /// All suffixes in the order of [`SUFFIX_ALIASES`].
+    pub const ALL: [UidSuffix; 6] = [
+        UidSuffix::MWST,
+        UidSuffix::TVA,
+        UidSuffix::IVA,
+        UidSuffix::VAT,
+        UidSuffix::HR,
+        UidSuffix::RC,
+    ];
+
+    /// Returns the suffix as written after the UID.
+    pub const fn as_str(&self) -> &'static str {
+        SUFFIX_ALIASES[*self as usize].0
+    }
+
+    /// Returns the German spelling the suffix stands for, see
+    /// [`SUFFIX_ALIASES`].
+    pub const fn canonical(&self) -> &'static str {
+        SUFFIX_ALIASES[*self as usize].1
+    }
+
+    /// Returns `true` for the VAT designations.
+    pub const fn is_vat(&self) -> bool {
+        matches!(
+            self,
+            UidSuffix::MWST | UidSuffix::TVA | UidSuffix::IVA | UidSuffix::VAT
+        )
+    }
+
+    /// Returns `true` for the commercial register designations.
+    pub const fn is_commercial_register(&self) -> bool {
+        matches!(self, UidSuffix::HR | UidSuffix::RC)
+    }
+}
+
+impl FromStr for UidSuffix {
+    type Err = UidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SUFFIX_ALIASES
+            .iter()
+            .position(|(alias, _)| alias.eq_ignore_ascii_case(s))
+            .map(|i| Self::ALL[i])
+            .ok_or(UidError::InvalidFormat("Unknown UID suffix", 0))
+    }
+}
+
+impl fmt::Display for UidSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Options controlling how strictly [`SwissUid::parse_with`] parses its input.
+///
+/// The default options are as tolerant as [`SwissUid::new`].
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::parse::ParseOptions;
+/// use swiss_uid::uid::{SwissUid, UidPrefix};
+///
+/// let strict = ParseOptions {
+///     reject_trailing: true,
+///     ..Default::default()
+/// };
+/// assert!(SwissUid::parse_with("CHE-109.322.551 MWST", &strict).is_ok());
+/// assert!(SwissUid::parse_with("CHE-109.322.5511234", &strict).is_err());
+/// assert!(SwissUid::parse_with("CHE-109.322.5511234", &ParseOptions::default()).is_ok());
+///
+/// let tolerant = ParseOptions {
+///     default_prefix: Some(UidPrefix::CHE),
+///     ..Default::default()
+/// };
+/// assert!(SwissUid::parse_with("109322551", &tolerant).is_ok());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Reject any characters after the check digit, except for whitespace and
+    /// a known suffix such as ` MWST` or ` HR`, see [`SUFFIX_ALIASES`].
+    pub reject_trailing: bool,
+    /// Accept the French and Italian prefixes `IDE` and `IDI` in place of
+    /// `CHE`, see [`UidPrefix::ALIASES`]. Their use is reported as a
+    /// [`ParseWarning::PrefixAlias`].
+    pub allow_prefix_aliases: bool,
+    /// Require the separators of the canonical form `CHE-109.322.551`, a dash
+    /// after the prefix and dots between the groups of digits.
+    pub require_canonical: bool,
+    /// Reject prefixes which are not written in uppercase, e.g. `che`.
+    pub reject_lowercase: bool,
+    /// The prefix assumed if the input starts with a digit instead of a
+    /// prefix, e.g. `109.322.551`. Its use is reported as a
+    /// [`ParseWarning::DefaultPrefix`].
+    pub default_prefix: Option<UidPrefix>,
+}
+
+/// Deviations from the canonical form which were accepted while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// The input used the given alias instead of the canonical prefix.
+    PrefixAlias(&'static str),
+    /// The input had no prefix and the default prefix was used.
+    DefaultPrefix(UidPrefix),
+    /// Invisible characters were removed, see [`sanitize_input`].
+    RemovedInvisible,
+}
+
+/// The warnings collected by [`SwissUid::parse_with_report`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    warnings: Vec<ParseWarning>,
+}
+
+impl ParseReport {
+    /// Returns the warnings in the order they occurred.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Returns `true` if the input was parsed without any warnings.
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl SwissUid {
+    /// Parses a UID from a string according to the given options.
+    pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Self, UidError> {
+        Self::parse_with_report(input, options).map(|(uid, _)| uid)
+    }
+
+    /// Parses a UID like [`SwissUid::new`] and returns the register suffix
+    /// written after it, if any.
+    ///
+    /// Trailing characters which are not a known suffix are ignored and
+    /// result in `None`, like they are ignored by [`SwissUid::new`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::parse::UidSuffix;
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let (uid, suffix) = SwissUid::parse_with_suffix("CHE-109.322.551 TVA").unwrap();
+    /// assert_eq!(uid.to_string(), "CHE-109.322.551");
+    /// assert_eq!(suffix, Some(UidSuffix::TVA));
+    /// assert!(suffix.unwrap().is_vat());
+    /// ```
+    pub fn parse_with_suffix(input: &str) -> Result<(Self, Option<UidSuffix>), UidError> {
+        let uid = Self::new(input)?;
+        let sanitized = sanitize_input(input);
+        // The same split as in `SwissUid::new`, which succeeded above
+        let (_, rest) = split_prefix(&sanitized)?;
+        let written = trailer(rest);
+        let suffix = written
+            .starts_with(char::is_whitespace)
+            .then(|| written.trim().parse().ok())
+            .flatten();
+        Ok((uid, suffix))
+    }
+
+    /// Parses a UID like [`SwissUid::parse_with`] and additionally reports
+    /// the accepted deviations from the canonical form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::parse::{ParseOptions, ParseWarning};
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let options = ParseOptions {
+    ///     allow_prefix_aliases: true,
+    ///     ..Default::default()
+    /// };
+    /// let (uid, report) = SwissUid::parse_with_report("IDE-109.322.551", &options).unwrap();
+    /// assert_eq!(uid.to_string(), "CHE-109.322.551");
+    /// assert_eq!(report.warnings(), [ParseWarning::PrefixAlias("IDE")]);
+    /// assert!(SwissUid::new("IDE-109.322.551").is_err());
+    /// ```
+    pub fn parse_with_report(
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<(Self, ParseReport), UidError> {
+        let mut report = ParseReport::default();
+        let sanitized = sanitize_input(input);
+        if let Cow::Owned(_) = sanitized {
+            report.warnings.push(ParseWarning::RemovedInvisible);
+        }
+        Self::parse_sanitized(&sanitized, options, &mut report)
+            .map(|uid| (uid, report))
+            .map_err(|e| restore_position(e, input))
+    }
+
+    /// Parses the sanitized input according to the options, with error
+    /// positions referring to the characters of `input`.
+    fn parse_sanitized(
+        input: &str,
+        options: &ParseOptions,
+        report: &mut ParseReport,
+    ) -> Result<Self, UidError> {
+        let (pfx, written, rest) = match options.default_prefix {
+            Some(pfx) if input.starts_with(|c: char| c.is_ascii_digit()) => {
+                report.warnings.push(ParseWarning::DefaultPrefix(pfx));
+                (pfx, "", input)
+            }
+            _ => {
+                let (Some(written), Some(rest)) = (input.get(..3), input.get(3..)) else {
+                    return Err(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0));
+                };
+                let pfx = match alias_prefix(written) {
+                    Some((alias, pfx)) if options.allow_prefix_aliases => {
+                        report.warnings.push(ParseWarning::PrefixAlias(alias));
+                        pfx
+                    }
+                    _ => UidPrefix::from_canonical(written)?,
+                };
+                (pfx, written, rest)
+            }
+        };
+        // The prefix is ASCII, so its length in bytes is its length in characters
+        let offset = written.len();
+
+        if options.reject_lowercase {
+            if let Some(i) = written.bytes().position(|b| b.is_ascii_lowercase()) {
+                return Err(UidError::InvalidFormat(
+                    "Prefix must be written in uppercase",
+                    i,
+                ));
+            }
+        }
+        let layout: &[u8] = if written.is_empty() {
+            b"000.000.000"
+        } else {
+            b"-000.000.000"
+        };
+        if options.require_canonical {
+            if let Some(i) = layout_mismatch(rest, layout) {
+                return Err(UidError::InvalidFormat(
+                    "UID must be formatted like CHE-109.322.551",
+                    offset + i,
+                ));
+            }
+        }
+        let uid = Self::parse_digits(pfx, rest).map_err(|e| e.offset(offset))?;
+
+        let trailer = trailer(rest);
+        if options.reject_trailing && !is_allowed_trailer(trailer) {
+            let end = offset + rest.chars().count();
+            let unexpected = trailer.trim_start().chars().count();
+            return Err(UidError::InvalidFormat(
+                "Unexpected characters after the check digit",
+                end - unexpected,
+            ));
+        }
+        Ok(uid)
+    }
+}
+
+/// Returns the alias and the prefix it stands for if the prefix is one of the
+/// [`UidPrefix::ALIASES`].
+fn alias_prefix(written: &str) -> Option<(&'static str, UidPrefix)> {
+    UidPrefix::ALIASES
+        .into_iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(written))
+}
+
+/// Returns the character index of the first character of the input which
+/// does not match the layout, where `0` stands for any digit, or `None` if the
+/// input starts with the layout.
+fn layout_mismatch(input: &str, layout: &[u8]) -> Option<usize> {
+    // The layout is ASCII, so the characters before a mismatch are single bytes
+    let matching = input
+        .bytes()
+        .zip(layout)
+        .take_while(|&(b, &l)| {
+            if l == b'0' {
+                b.is_ascii_digit()
+            } else {
+                b == l
+            }
+        })
+        .count();
+    (matching < layout.len()).then_some(matching)
+}
+
+/// Returns the part of the input after the check digit (the 9th digit).
+fn trailer(digits: &str) -> &str {
+    digits
+        .char_indices()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .nth(8)
+        .map_or("", |(i, _)| &digits[i + 1..])
+}
+
+/// Returns `true` if the trailer is empty, whitespace or a known suffix.
+fn is_allowed_trailer(trailer: &str) -> bool {
+    let suffix = trailer.trim();
+    suffix.is_empty()
+        || (trailer.starts_with(char::is_whitespace) && suffix.parse::<UidSuffix>().is_ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reject_trailing() {
+        let options = ParseOptions {
+            reject_trailing: true,
+            ..Default::default()
+        };
+        for input in [
+            "CHE-109.322.551",
+            "CHE-109.322.551 ",
+            "CHE-109.322.551 MWST",
+            "CHE-109.322.551 mwst",
+            "CHE-109.322.551  HR ",
+            "CHE-109.322.551 TVA",
+            "CHE-109.322.551 IVA",
+            "CHE-109.322.551 rc",
+        ] {
+            assert!(SwissUid::parse_with(input, &options).is_ok(), "{}", input);
+        }
+        for (input, position) in [
+            ("CHE-109.322.5511234", 15),
+            ("CHE-109.322.551MWST", 15),
+            ("CHE-109.322.551 MWST HR", 16),
+            ("CHE-109.322.551, CHE-100.002.005", 15),
+        ] {
+            assert_eq!(
+                SwissUid::parse_with(input, &options),
+                Err(UidError::InvalidFormat(
+                    "Unexpected characters after the check digit",
+                    position
+                )),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_is_tolerant() {
+        let options = ParseOptions::default();
+        let uid = SwissUid::parse_with("CHE-109.322.5511234", &options).unwrap();
+        assert_eq!(uid.to_string(), "CHE-109.322.551");
+    }
+
+    #[test]
+    fn test_prefix_aliases() {
+        let options = ParseOptions {
+            allow_prefix_aliases: true,
+            reject_trailing: true,
+            ..Default::default()
+        };
+        let canonical = SwissUid::new("CHE-109.322.551").unwrap();
+        for (input, alias) in [("IDE-109.322.551 TVA", "IDE"), ("idi 109 322 551", "IDI")] {
+            let (uid, report) = SwissUid::parse_with_report(input, &options).unwrap();
+            assert_eq!(uid, canonical);
+            assert_eq!(report.warnings(), [ParseWarning::PrefixAlias(alias)]);
+        }
+
+        let (_, report) = SwissUid::parse_with_report("CHE-109.322.551", &options).unwrap();
+        assert!(report.is_clean());
+        assert_eq!(
+            SwissUid::parse_with("IDE-109.322.551", &ParseOptions::default()),
+            Err(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0))
+        );
+    }
+
+    #[test]
+    fn test_require_canonical() {
+        let options = ParseOptions {
+            require_canonical: true,
+            default_prefix: Some(UidPrefix::ADM),
+            ..Default::default()
+        };
+        for input in ["CHE-109.322.551", "che-109.322.551 MWST", "100.000.029"] {
+            assert!(SwissUid::parse_with(input, &options).is_ok(), "{}", input);
+        }
+        for (input, position) in [
+            ("CHE 109 322 551", 3),
+            ("CHE109322551", 3),
+            ("CHE-109.322.55", 14),
+            ("CHE--109.322.551", 4),
+            ("100 000 029", 3),
+        ] {
+            assert_eq!(
+                SwissUid::parse_with(input, &options),
+                Err(UidError::InvalidFormat(
+                    "UID must be formatted like CHE-109.322.551",
+                    position
+                )),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_reject_lowercase() {
+        let options = ParseOptions {
+            reject_lowercase: true,
+            allow_prefix_aliases: true,
+            ..Default::default()
+        };
+        assert!(SwissUid::parse_with("CHE 109 322 551", &options).is_ok());
+        assert!(SwissUid::parse_with("IDE 109 322 551", &options).is_ok());
+        for (input, position) in [
+            ("che-109.322.551", 0),
+            ("Che-109.322.551", 1),
+            ("ide-109.322.551", 0),
+        ] {
+            assert_eq!(
+                SwissUid::parse_with(input, &options),
+                Err(UidError::InvalidFormat(
+                    "Prefix must be written in uppercase",
+                    position
+                )),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let options = ParseOptions {
+            allow_prefix_aliases: true,
+            default_prefix: Some(UidPrefix::CHE),
+            reject_trailing: true,
+            ..Default::default()
+        };
+        for (input, position) in [
+            ("IDE-109.322.552", 14),
+            ("idi 010 322 557", 4),
+            ("109.322.552", 10),
+            ("109322552", 8),
+            ("010322557", 0),
+            ("109.322", 7),
+            ("XYZ-109.322.551", 0),
+            ("\u{200b}IDE-109.322.552", 15),
+            ("CHE-\u{feff}109.322.551 MWST?", 17),
+        ] {
+            let err = SwissUid::parse_with_report(input, &options).unwrap_err();
+            assert_eq!(err.position(), position, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_default_prefix() {
+        let options = ParseOptions {
+            default_prefix: Some(UidPrefix::CHE),
+            reject_trailing: true,
+            ..Default::default()
+        };
+        let (uid, report) = SwissUid::parse_with_report("109322551 MWST", &options).unwrap();
+        assert_eq!(uid.to_string(), "CHE-109.322.551");
+        assert_eq!(
+            report.warnings(),
+            [ParseWarning::DefaultPrefix(UidPrefix::CHE)]
+        );
+
+        let (uid, report) = SwissUid::parse_with_report("ADM-100.000.029", &options).unwrap();
+        assert_eq!(uid.to_string(), "ADM-100.000.029");
+        assert!(report.is_clean());
+
+        assert!(SwissUid::parse_with("109322551", &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_suffixes_match_aliases() {
+        assert_eq!(UidSuffix::ALL.len(), SUFFIX_ALIASES.len());
+        for (suffix, (alias, canonical)) in UidSuffix::ALL.into_iter().zip(SUFFIX_ALIASES) {
+            assert_eq!(suffix.as_str(), alias);
+            assert_eq!(suffix.canonical(), canonical);
+            assert_eq!(alias.parse(), Ok(suffix));
+            let expected = if suffix.is_vat() {
+                SUFFIX_MWST
+            } else {
+                SUFFIX_HR
+            };
+            assert_eq!(canonical, expected, "{}", alias);
+        }
+    }
+
+    #[test]
+    fn test_parse_with_suffix() {
+        for suffix in UidSuffix::ALL {
+            let input = format!("CHE-109.322.551 {}", suffix.as_str().to_lowercase());
+            assert_eq!(
+                SwissUid::parse_with_suffix(&input).map(|(_, s)| s),
+                Ok(Some(suffix))
+            );
+        }
+        for input in [
+            "CHE-109.322.551",
+            "CHE-109.322.551 ",
+            "CHE-109.322.551MWST",
+            "CHE-109.322.551 MWST HR",
+            "\u{feff}CHE-109.322.551MWST",
+        ] {
+            assert_eq!(
+                SwissUid::parse_with_suffix(input).map(|(_, s)| s),
+                Ok(None),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            SwissUid::parse_with_suffix("\u{feff}CHE-109.322.551\u{200b} MWST").map(|(_, s)| s),
+            Ok(Some(UidSuffix::MWST))
+        );
+        assert!(SwissUid::parse_with_suffix("CHE-109.322.552 MWST").is_err());
+        assert!(UidSuffix::RC.is_commercial_register() && !UidSuffix::RC.is_vat());
+    }
+
+    #[test]
+    fn test_sanitize_input() {
+        let pasted = "\u{202a}CHE-109.322.551\u{202c}\u{200b} MWST";
+        assert_eq!(sanitize_input(pasted), "CHE-109.322.551 MWST");
+        assert!(matches!(
+            sanitize_input("CHE-109.322.551"),
+            Cow::Borrowed(_)
+        ));
+
+        let options = ParseOptions {
+            reject_trailing: true,
+            require_canonical: true,
+            ..Default::default()
+        };
+        let (uid, report) = SwissUid::parse_with_report(pasted, &options).unwrap();
+        assert_eq!(uid.to_string(), "CHE-109.322.551");
+        assert_eq!(report.warnings(), [ParseWarning::RemovedInvisible]);
+        assert_eq!(
+            SwissUid::new("\u{feff}ADM-100.000.029")
+                .unwrap()
+                .to_string(),
+            "ADM-100.000.029"
+        );
+        assert_eq!(
+            SwissUid::parse_with_suffix(pasted).map(|(_, suffix)| suffix),
+            Ok(Some(UidSuffix::MWST))
+        );
+    }
+}
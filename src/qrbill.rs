@@ -0,0 +1,200 @@
+//! Helpers for the UID as it appears in Swiss QR-bill (SPC) payloads.
+//!
+//! The structured billing information of a QR-bill follows the Swico `S1`
+//! syntax, e.g. `//S1/10/10201409/11/190512/30/109322551/32/7.7`. Tag `30`
+//! carries the UID of the creditor as its 9 digits, without the `CHE` prefix,
+//! separators or a VAT suffix.
+
+use crate::uid::{SwissUid, UidError, UidPrefix};
+
+/// Leading marker of structured billing information in the Swico `S1` syntax.
+pub const S1_PREFIX: &str = "//S1";
+
+/// Tag of the creditor's UID in the Swico `S1` syntax.
+pub const S1_TAG_UID: &str = "30";
+
+/// Returns the UID as the bare 9 digits used as value of tag `30`.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{qrbill, uid::SwissUid};
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(qrbill::to_s1_value(&uid), "109322551");
+/// ```
+pub fn to_s1_value(uid: &SwissUid) -> String {
+    uid.to_string()
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect()
+}
+
+/// Returns the UID as a complete `S1` tag/value pair (`/30/109322551`) which
+/// can be appended to the structured billing information.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{qrbill, uid::SwissUid};
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// let info = format!("//S1/10/10201409{}", qrbill::to_s1_field(&uid));
+/// assert_eq!(info, "//S1/10/10201409/30/109322551");
+/// ```
+pub fn to_s1_field(uid: &SwissUid) -> String {
+    format!("/{}/{}", S1_TAG_UID, to_s1_value(uid))
+}
+
+/// Extracts and validates the UID from structured billing information in the
+/// Swico `S1` syntax.
+///
+/// Returns `Ok(None)` if the billing information does not contain tag `30`.
+/// The position of an error refers to the characters of `billing_info`.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{qrbill, uid::SwissUid};
+///
+/// let uid = qrbill::parse_s1("//S1/10/10201409/11/190512/30/109322551/32/7.7").unwrap();
+/// assert_eq!(uid, Some(SwissUid::new("CHE-109.322.551").unwrap()));
+/// ```
+pub fn parse_s1(billing_info: &str) -> Result<Option<SwissUid>, UidError> {
+    let fields = billing_info
+        .strip_prefix(S1_PREFIX)
+        .and_then(|s| s.strip_prefix('/'))
+        .ok_or(UidError::InvalidFormat(
+            "Billing information must start with '//S1/'",
+            0,
+        ))?;
+
+    let mut tokens = split_unescaped(fields);
+    while let Some((_, tag)) = tokens.next() {
+        let (start, value) = tokens.next().unwrap_or((fields.len(), ""));
+        if tag == S1_TAG_UID {
+            let offset = billing_info.len() - fields.len() + start;
+            return from_s1_value(value)
+                .map(Some)
+                .map_err(|e| e.offset(billing_info[..offset].chars().count()));
+        }
+    }
+    Ok(None)
+}
+
+/// Parses the value of tag `30` (the 9 bare digits) into a UID.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::qrbill;
+///
+/// let uid = qrbill::from_s1_value("109322551").unwrap();
+/// assert_eq!(uid.to_string(), "CHE-109.322.551");
+/// ```
+pub fn from_s1_value(value: &str) -> Result<SwissUid, UidError> {
+    let invalid = value
+        .chars()
+        .position(|c| !c.is_ascii_digit())
+        .or_else(|| (value.len() != 9).then(|| value.len().min(9)));
+    if let Some(i) = invalid {
+        return Err(UidError::InvalidFormat(
+            "UID in QR-bill must consist of exactly 9 digits",
+            i,
+        ));
+    }
+    // All characters are digits, so the digit indices are the character indices
+    SwissUid::from_digit_iter(UidPrefix::CHE, value.bytes().map(|b| b - b'0'))
+}
+
+/// Splits the `S1` fields on `/`, honouring the `\/` escape sequence, and
+/// returns each field with its byte offset in `s`.
+fn split_unescaped(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = Some(0);
+    std::iter::from_fn(move || {
+        let offset = start?;
+        let field = &s[offset..];
+        let bytes = field.as_bytes();
+        // Scan from the left so the byte after each `\` is skipped, which
+        // keeps the `/` in `\\/` a separator
+        let mut end = None;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 1,
+                b'/' => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        match end {
+            Some(i) => {
+                start = Some(offset + i + 1);
+                Some((offset, &field[..i]))
+            }
+            None => {
+                start = None;
+                Some((offset, field))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::uid::CheckDigitMismatch;
+
+    #[test]
+    fn test_roundtrip_s1() {
+        let uid = SwissUid::new("CHE-109.322.551").unwrap();
+        let info = format!("//S1/10/10201409{}/31/180508", to_s1_field(&uid));
+        assert_eq!(info, "//S1/10/10201409/30/109322551/31/180508");
+        assert_eq!(parse_s1(&info), Ok(Some(uid)));
+    }
+
+    #[test]
+    fn test_parse_s1_with_escaped_values() {
+        let info = r"//S1/10/10\/2014\/09/30/109322551";
+        let uid = parse_s1(info).unwrap().unwrap();
+        assert_eq!(uid.to_string(), "CHE-109.322.551");
+
+        // An escaped backslash does not escape the following separator
+        let info = r"//S1/10/10201409\\/30/109322551";
+        let uid = parse_s1(info).unwrap().unwrap();
+        assert_eq!(uid.to_string(), "CHE-109.322.551");
+    }
+
+    #[test]
+    fn test_parse_s1_without_uid() {
+        assert_eq!(parse_s1("//S1/10/10201409/11/190512"), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_s1_invalid() {
+        assert_eq!(
+            parse_s1("//S1/30/109322552"),
+            Err(UidError::MismatchedCheckDigit(
+                CheckDigitMismatch::new(1, 2),
+                16
+            ))
+        );
+        assert_eq!(
+            parse_s1("//S1/30/CHE-109.322.551"),
+            Err(UidError::InvalidFormat(
+                "UID in QR-bill must consist of exactly 9 digits",
+                8
+            ))
+        );
+        assert_eq!(
+            parse_s1("//S2/30/109322551"),
+            Err(UidError::InvalidFormat(
+                "Billing information must start with '//S1/'",
+                0
+            ))
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Lookups in sorted slices of UIDs, e.g. an allow-list kept as a static slice.
+//!
+//! The slices must be sorted by [`sort_key`], which can be done with
+//! [`sort_uids`]. The key is built from the packed digits directly and sorts
+//! like the canonical strings.
+
+use crate::uid::SwissUid;
+
+/// Returns the key the slices of this module are sorted by.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{search::sort_key, uid::SwissUid};
+///
+/// let adm = SwissUid::new("ADM-999.999.996").unwrap();
+/// let che = SwissUid::new("CHE-100.000.006").unwrap();
+/// assert!(sort_key(&adm) < sort_key(&che));
+/// ```
+#[inline]
+pub fn sort_key(uid: &SwissUid) -> u64 {
+    uid.packed_key()
+}
+
+/// Sorts the UIDs by [`sort_key`].
+pub fn sort_uids(uids: &mut [SwissUid]) {
+    uids.sort_unstable_by_key(sort_key);
+}
+
+/// Returns `true` if the UIDs are sorted by [`sort_key`].
+pub fn is_sorted(uids: &[SwissUid]) -> bool {
+    uids.windows(2)
+        .all(|pair| sort_key(&pair[0]) <= sort_key(&pair[1]))
+}
+
+/// Binary searches a slice sorted by [`sort_key`] for the UID.
+///
+/// Like [`slice::binary_search`], returns `Ok` with the index of a matching
+/// UID or `Err` with the index where it could be inserted while keeping the
+/// slice sorted.
+///
+/// The slice is not checked for being sorted, since that would make every
+/// lookup linear. For an unsorted slice the result is unspecified; check the
+/// slice once with [`is_sorted`] when it is built instead.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::{search, uid::SwissUid};
+///
+/// let mut allowed: Vec<SwissUid> = ["CHE-109.322.551", "ADM-100.000.029", "CHE-100.000.006"]
+///     .iter()
+///     .map(|s| SwissUid::new(s).unwrap())
+///     .collect();
+/// search::sort_uids(&mut allowed);
+///
+/// let needle = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(search::binary_search_uids(&allowed, &needle), Ok(2));
+/// ```
+pub fn binary_search_uids(uids: &[SwissUid], needle: &SwissUid) -> Result<usize, usize> {
+    let key = sort_key(needle);
+    uids.binary_search_by_key(&key, sort_key)
+}
+
+/// Returns `true` if the slice sorted by [`sort_key`] contains the UID.
+pub fn contains(uids: &[SwissUid], needle: &SwissUid) -> bool {
+    binary_search_uids(uids, needle).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::uid::UidPrefix;
+
+    #[test]
+    fn test_sort_key_matches_u64_order() {
+        let mut uids: Vec<SwissUid> = SwissUid::iter_all(UidPrefix::CHE)
+            .step_by(997)
+            .take(200)
+            .chain(SwissUid::iter_all(UidPrefix::ADM).take(200))
+            .collect();
+        sort_uids(&mut uids);
+        assert!(uids.windows(2).all(|w| w[0].to_u64() < w[1].to_u64()));
+        assert!(uids.windows(2).all(|w| w[0].to_string() < w[1].to_string()));
+    }
+
+    #[test]
+    fn test_binary_search_uids() {
+        let uids: Vec<SwissUid> = SwissUid::iter_all(UidPrefix::CHE)
+            .step_by(2)
+            .take(100)
+            .collect();
+        assert!(is_sorted(&uids));
+        for (i, uid) in uids.iter().enumerate() {
+            assert_eq!(binary_search_uids(&uids, uid), Ok(i));
+        }
+
+        let missing = SwissUid::iter_all(UidPrefix::CHE).nth(1).unwrap();
+        assert_eq!(binary_search_uids(&uids, &missing), Err(1));
+        assert!(!contains(&uids, &missing));
+        let adm = SwissUid::new("ADM-999.999.996").unwrap();
+        assert_eq!(binary_search_uids(&uids, &adm), Err(0));
+    }
+}
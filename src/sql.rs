@@ -0,0 +1,198 @@
+//! SQL snippets implementing the UID validation inside a database.
+//!
+//! The expressions are generated from the same factor table as
+//! [`calculate_checkdigit`](crate::uid::calculate_checkdigit), so database
+//! constraints cannot drift from the Rust implementation. They validate values
+//! stored in the canonical form `CHE-109.322.551`.
+
+use crate::uid::{CANONICAL_DIGIT_POS, DIGIT_FACTORS};
+
+/// The SQL dialect to generate snippets for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    /// MySQL 8.0 or later.
+    MySql,
+}
+
+/// Returns a boolean SQL expression validating the canonical UID in `column`.
+///
+/// The expression can be used in a `CHECK` constraint or a `WHERE` clause.
+/// The check digit is only calculated for values matching the canonical
+/// format, guarded by a `CASE` because the databases do not guarantee the
+/// evaluation order of `AND`. Other values, including `NULL`, evaluate to
+/// `false`, so nullable columns need an additional `uid IS NULL OR`.
+///
+/// `column` is inserted into the SQL as is and must be a trusted, plain
+/// identifier, optionally qualified like `company.uid`.
+///
+/// # Panics
+///
+/// Panics if `column` is not a plain identifier, e.g. because it contains
+/// quotes, spaces or other SQL.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::sql::{check_expression, Dialect};
+///
+/// let expr = check_expression(Dialect::Postgres, "uid");
+/// let ddl = format!("ALTER TABLE company ADD CONSTRAINT uid_valid CHECK ({})", expr);
+/// assert!(ddl.starts_with("ALTER TABLE company ADD CONSTRAINT uid_valid CHECK (CASE WHEN uid ~ '^(CHE|ADM)-"));
+/// ```
+pub fn check_expression(dialect: Dialect, column: &str) -> String {
+    assert_identifier(column);
+    let pattern = "'^(CHE|ADM)-[1-9][0-9]{2}[.][0-9]{3}[.][0-9]{3}$'";
+    let format_check = match dialect {
+        Dialect::Postgres => format!("{} ~ {}", column, pattern),
+        // The match type `c` matches case-sensitively regardless of the collation
+        Dialect::MySql => format!("REGEXP_LIKE({}, {}, 'c')", column, pattern),
+    };
+
+    let checksum = DIGIT_FACTORS
+        .iter()
+        .zip(CANONICAL_DIGIT_POS.iter())
+        .map(|(factor, &pos)| format!("{} * {}", factor, digit_at(dialect, column, pos)))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let checkdigit = digit_at(dialect, column, CANONICAL_DIGIT_POS[DIGIT_FACTORS.len()]);
+
+    // A remainder of 0 yields check digit 0, a calculated check digit of 10 can
+    // never match a single digit and is thus rejected as well.
+    format!(
+        "CASE WHEN {} THEN (11 - ({}) % 11) % 11 = {} ELSE false END",
+        format_check, checksum, checkdigit
+    )
+}
+
+/// Returns a `CREATE FUNCTION` statement for a function `name(uid)` returning
+/// whether the given canonical UID is valid.
+///
+/// `name` is inserted into the SQL as is and must be a trusted, plain
+/// identifier, optionally qualified with a schema like `app.swiss_uid_valid`.
+///
+/// # Panics
+///
+/// Panics if `name` is not a plain identifier.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::sql::{create_function, Dialect};
+///
+/// let ddl = create_function(Dialect::MySql, "swiss_uid_valid");
+/// assert!(ddl.starts_with("CREATE FUNCTION swiss_uid_valid(uid VARCHAR(15)) RETURNS BOOLEAN"));
+/// ```
+pub fn create_function(dialect: Dialect, name: &str) -> String {
+    assert_identifier(name);
+    let expr = check_expression(dialect, "uid");
+    match dialect {
+        Dialect::Postgres => format!(
+            "CREATE FUNCTION {}(uid text) RETURNS boolean LANGUAGE sql IMMUTABLE AS $$ SELECT {} $$;",
+            name, expr
+        ),
+        Dialect::MySql => format!(
+            "CREATE FUNCTION {}(uid VARCHAR(15)) RETURNS BOOLEAN DETERMINISTIC RETURN {};",
+            name, expr
+        ),
+    }
+}
+
+/// Panics unless `name` is a plain, optionally qualified identifier, which can
+/// be inserted into SQL without quoting.
+fn assert_identifier(name: &str) {
+    let is_plain = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+    };
+    assert!(
+        name.split('.').all(is_plain),
+        "`{}` is not a plain SQL identifier",
+        name
+    );
+}
+
+/// Returns the expression extracting the digit at the 0-based byte `pos`.
+fn digit_at(dialect: Dialect, column: &str, pos: usize) -> String {
+    match dialect {
+        Dialect::Postgres => format!("substr({}, {}, 1)::int", column, pos + 1),
+        Dialect::MySql => format!("CAST(SUBSTRING({}, {}, 1) AS UNSIGNED)", column, pos + 1),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_expression_postgres() {
+        assert_eq!(
+            check_expression(Dialect::Postgres, "uid"),
+            "CASE WHEN uid ~ '^(CHE|ADM)-[1-9][0-9]{2}[.][0-9]{3}[.][0-9]{3}$' THEN (11 - (\
+             5 * substr(uid, 5, 1)::int + 4 * substr(uid, 6, 1)::int + \
+             3 * substr(uid, 7, 1)::int + 2 * substr(uid, 9, 1)::int + \
+             7 * substr(uid, 10, 1)::int + 6 * substr(uid, 11, 1)::int + \
+             5 * substr(uid, 13, 1)::int + 4 * substr(uid, 14, 1)::int\
+             ) % 11) % 11 = substr(uid, 15, 1)::int ELSE false END"
+        );
+    }
+
+    #[test]
+    fn test_check_expression_mysql() {
+        let expr = check_expression(Dialect::MySql, "company.uid");
+        assert!(expr.starts_with("CASE WHEN REGEXP_LIKE(company.uid, '^(CHE|ADM)-"));
+        assert!(expr.contains("[0-9]{3}$', 'c') THEN "));
+        assert!(expr.contains("5 * CAST(SUBSTRING(company.uid, 5, 1) AS UNSIGNED)"));
+        assert!(expr.ends_with("= CAST(SUBSTRING(company.uid, 15, 1) AS UNSIGNED) ELSE false END"));
+    }
+
+    #[test]
+    fn test_create_function_postgres() {
+        let ddl = create_function(Dialect::Postgres, "swiss_uid_valid");
+        assert!(ddl.starts_with(
+            "CREATE FUNCTION swiss_uid_valid(uid text) RETURNS boolean LANGUAGE sql IMMUTABLE AS $$ SELECT CASE WHEN uid ~ "
+        ));
+        assert!(ddl.ends_with(" ELSE false END $$;"));
+    }
+
+    #[test]
+    fn test_identifiers() {
+        check_expression(Dialect::Postgres, "_uid2");
+        create_function(Dialect::MySql, "app.swiss_uid_valid");
+        for name in [
+            "",
+            "uid; DROP TABLE company",
+            "\"uid\"",
+            "company.",
+            "1uid",
+            "uid--",
+        ] {
+            let result = ::std::panic::catch_unwind(|| check_expression(Dialect::Postgres, name));
+            assert!(result.is_err(), "{}", name);
+            let result = ::std::panic::catch_unwind(|| create_function(Dialect::MySql, name));
+            assert!(result.is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_casts_are_guarded_by_the_format_check() {
+        for dialect in [Dialect::Postgres, Dialect::MySql] {
+            for sql in [
+                check_expression(dialect, "uid"),
+                create_function(dialect, "swiss_uid_valid"),
+            ] {
+                // Every cast must be in the THEN branch, after the format check
+                let then = sql.find(" THEN ").expect("missing CASE guard");
+                let guard = &sql[..then];
+                assert!(guard.contains("CASE WHEN "), "{}", sql);
+                assert!(
+                    !guard.contains("substr") && !guard.contains("SUBSTRING"),
+                    "{}",
+                    sql
+                );
+                assert!(sql.contains(" ELSE false END"), "{}", sql);
+                assert!(!sql.contains(" AND "), "{}", sql);
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Counters collected while validating many UIDs.
+
+use ::std::{collections::HashSet, ops::AddAssign, time::Duration, time::Instant};
+
+use crate::uid::{SwissUid, UidError, UidErrorKind};
+
+/// Statistics of a validation run: valid UIDs, invalid ones per error kind,
+/// duplicates and throughput.
+///
+/// Statistics of several shards can be merged with [`ValidationStats::merge`]
+/// (or `+=`), so distributed jobs can aggregate their results cheaply.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::stats::validate_all;
+/// use swiss_uid::uid::UidErrorKind;
+///
+/// let mut stats = validate_all(["CHE-109.322.551", "CHE-109.322.552"]);
+/// stats += validate_all(["CHE-109.322.551", "ABC-109.322.551"]);
+/// assert_eq!(stats.total(), 4);
+/// assert_eq!(stats.valid(), 2);
+/// assert_eq!(stats.errors(UidErrorKind::MismatchedCheckDigit), 1);
+/// assert_eq!(stats.errors(UidErrorKind::InvalidFormat), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationStats {
+    valid: u64,
+    errors: [u64; 4],
+    duplicates: u64,
+    elapsed: Duration,
+}
+
+impl ValidationStats {
+    /// Creates empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a valid UID.
+    pub fn record_valid(&mut self) {
+        self.valid += 1;
+    }
+
+    /// Counts an invalid UID.
+    pub fn record_error(&mut self, kind: UidErrorKind) {
+        self.errors[Self::kind_index(kind)] += 1;
+    }
+
+    /// Counts a valid UID which was already seen before. Duplicates are
+    /// counted as valid as well.
+    pub fn record_duplicate(&mut self) {
+        self.duplicates += 1;
+    }
+
+    /// Counts the result of a validation.
+    pub fn record<T>(&mut self, result: &Result<T, UidError>) {
+        match result {
+            Ok(_) => self.record_valid(),
+            Err(e) => self.record_error(e.kind()),
+        }
+    }
+
+    /// Adds processing time to the statistics, e.g. the time a whole batch
+    /// took.
+    pub fn add_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed += elapsed;
+    }
+
+    /// Returns the number of valid UIDs, including duplicates.
+    pub fn valid(&self) -> u64 {
+        self.valid
+    }
+
+    /// Returns the number of invalid UIDs with the given error kind.
+    pub fn errors(&self, kind: UidErrorKind) -> u64 {
+        self.errors[Self::kind_index(kind)]
+    }
+
+    /// Returns the number of invalid UIDs.
+    pub fn invalid(&self) -> u64 {
+        self.errors.iter().sum()
+    }
+
+    /// Returns the number of valid UIDs which were seen before.
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    /// Returns the number of validated UIDs.
+    pub fn total(&self) -> u64 {
+        self.valid + self.invalid()
+    }
+
+    /// Returns the processing time.
+    ///
+    /// After a [`merge`](Self::merge) this is the sum of the processing times
+    /// of all shards, not the wall time of shards running in parallel.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of validated UIDs per second of
+    /// [`elapsed`](Self::elapsed), or `None` if no time was recorded.
+    pub fn throughput(&self) -> Option<f64> {
+        let secs = self.elapsed.as_secs_f64();
+        (secs > 0.0).then(|| self.total() as f64 / secs)
+    }
+
+    /// Adds the counters of `other`, e.g. of another shard. The elapsed times
+    /// are summed up as well, so for shards running in parallel the
+    /// throughput is per shard rather than of the whole job.
+    pub fn merge(&mut self, other: &ValidationStats) {
+        self.valid += other.valid;
+        for (e, o) in self.errors.iter_mut().zip(other.errors) {
+            *e += o;
+        }
+        self.duplicates += other.duplicates;
+        self.elapsed += other.elapsed;
+    }
+
+    fn kind_index(kind: UidErrorKind) -> usize {
+        match kind {
+            UidErrorKind::InvalidFormat => 0,
+            UidErrorKind::LeadingZeroNotAllowed => 1,
+            UidErrorKind::InvalidCheckDigit => 2,
+            UidErrorKind::MismatchedCheckDigit => 3,
+        }
+    }
+}
+
+impl AddAssign for ValidationStats {
+    fn add_assign(&mut self, other: Self) {
+        self.merge(&other);
+    }
+}
+
+/// Validates many UIDs while keeping [`ValidationStats`] including duplicates.
+///
+/// The validator does not measure time, the caller can add the duration of a
+/// batch with [`ValidationStats::add_elapsed`].
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::stats::BatchValidator;
+///
+/// let mut validator = BatchValidator::new();
+/// assert!(validator.validate("CHE-109.322.551").is_ok());
+/// assert!(validator.validate("CHE 109 322 551").is_ok());
+/// assert_eq!(validator.stats().valid(), 2);
+/// assert_eq!(validator.stats().duplicates(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BatchValidator {
+    seen: HashSet<u64>,
+    stats: ValidationStats,
+}
+
+impl BatchValidator {
+    /// Creates a validator with empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses and validates the input and updates the statistics.
+    pub fn validate(&mut self, input: &str) -> Result<SwissUid, UidError> {
+        let result = SwissUid::new(input);
+        self.stats.record(&result);
+        if let Ok(uid) = result {
+            if !self.seen.insert(uid.to_u64()) {
+                self.stats.record_duplicate();
+            }
+        }
+        result
+    }
+
+    /// Returns the statistics collected so far.
+    pub fn stats(&self) -> &ValidationStats {
+        &self.stats
+    }
+
+    /// Consumes the validator and returns its statistics.
+    pub fn into_stats(self) -> ValidationStats {
+        self.stats
+    }
+}
+
+/// Validates all inputs and returns the statistics, with the time the whole
+/// batch took as elapsed time.
+pub fn validate_all<I, S>(inputs: I) -> ValidationStats
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let start = Instant::now();
+    let mut validator = BatchValidator::new();
+    for input in inputs {
+        let _ = validator.validate(input.as_ref());
+    }
+    let mut stats = validator.into_stats();
+    stats.add_elapsed(start.elapsed());
+    stats
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_all() {
+        let stats = validate_all([
+            "CHE-109.322.551",
+            "CHE-109.322.551",
+            "ADM-109.322.551",
+            "CHE-010.322.557",
+            "CHE-100.000.160",
+            "CHE-109.322.552",
+            "CHE-109",
+        ]);
+        assert_eq!(stats.total(), 7);
+        assert_eq!(stats.valid(), 3);
+        assert_eq!(stats.duplicates(), 1);
+        assert_eq!(stats.invalid(), 4);
+        assert_eq!(stats.errors(UidErrorKind::InvalidFormat), 1);
+        assert_eq!(stats.errors(UidErrorKind::LeadingZeroNotAllowed), 1);
+        assert_eq!(stats.errors(UidErrorKind::InvalidCheckDigit), 1);
+        assert_eq!(stats.errors(UidErrorKind::MismatchedCheckDigit), 1);
+    }
+
+    #[test]
+    fn test_batch_validator_does_not_time() {
+        let mut validator = BatchValidator::new();
+        let _ = validator.validate("CHE-109.322.551");
+        assert_eq!(validator.stats().elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = ValidationStats::new();
+        a.record_valid();
+        a.record_error(UidErrorKind::InvalidFormat);
+        a.add_elapsed(Duration::from_secs(1));
+        let mut b = ValidationStats::new();
+        b.record_valid();
+        b.record_duplicate();
+        b.add_elapsed(Duration::from_secs(1));
+
+        a += b;
+        assert_eq!(a.valid(), 2);
+        assert_eq!(a.invalid(), 1);
+        assert_eq!(a.duplicates(), 1);
+        assert_eq!(a.elapsed(), Duration::from_secs(2));
+        assert_eq!(a.throughput(), Some(1.5));
+        assert_eq!(ValidationStats::new().throughput(), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Reference test vectors for downstream test suites.
+//!
+//! Contains the example of the eCH-0097 standard and a curated set of tricky
+//! vectors: the boundaries of the number space, check digit 0 (remainder 0)
+//! and main digits whose check digit would be 10.
+//!
+//! # Example
+//!
+//! ```rust
+//! use swiss_uid::test_vectors::{INVALID, VALID};
+//! use swiss_uid::uid::SwissUid;
+//!
+//! for v in VALID {
+//!     assert_eq!(SwissUid::new(v.input).unwrap().to_string(), v.canonical);
+//! }
+//! for v in INVALID {
+//!     assert_eq!(SwissUid::new(v.input).unwrap_err().kind(), v.kind);
+//! }
+//! ```
+
+use crate::uid::UidErrorKind;
+
+/// A valid input and its canonical representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidVector {
+    pub input: &'static str,
+    pub canonical: &'static str,
+}
+
+/// An invalid input and the kind of error it must be rejected with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidVector {
+    pub input: &'static str,
+    pub kind: UidErrorKind,
+}
+
+/// The example UID used throughout the eCH-0097 standard.
+pub const ECH_EXAMPLE: &str = "CHE-123.456.788";
+
+/// Valid UIDs in various input formats.
+pub const VALID: &[ValidVector] = &[
+    // Example of the eCH-0097 standard
+    valid(ECH_EXAMPLE, ECH_EXAMPLE),
+    valid("CHE-109.322.551", "CHE-109.322.551"),
+    valid("ADM-109.322.551", "ADM-109.322.551"),
+    // Lowest and highest numbers
+    valid("CHE-100.000.006", "CHE-100.000.006"),
+    valid("CHE-999.999.996", "CHE-999.999.996"),
+    // Remainder 0, resulting in check digit 0 instead of 11
+    valid("CHE-100.000.070", "CHE-100.000.070"),
+    // Zeroes within the number
+    valid("CHE-100.002.005", "CHE-100.002.005"),
+    // Tolerated input formats
+    valid("CHE109322551", "CHE-109.322.551"),
+    valid("che-109.322.551", "CHE-109.322.551"),
+    valid("CHE 109 322 551", "CHE-109.322.551"),
+    valid("CHE-109.322.551 MWST", "CHE-109.322.551"),
+    valid("CHE-109.322.551 HR", "CHE-109.322.551"),
+];
+
+/// Invalid UIDs and the kind of error they must be rejected with.
+pub const INVALID: &[InvalidVector] = &[
+    // Check digit does not match
+    invalid("CHE-109.322.552", UidErrorKind::MismatchedCheckDigit),
+    invalid("CHE-123.456.789", UidErrorKind::MismatchedCheckDigit),
+    invalid("CHE-100.000.000", UidErrorKind::MismatchedCheckDigit),
+    // Main digits whose check digit would be 10
+    invalid("CHE-100.000.160", UidErrorKind::InvalidCheckDigit),
+    invalid("CHE-999.999.980", UidErrorKind::InvalidCheckDigit),
+    // Leading zero
+    invalid("CHE-010.322.557", UidErrorKind::LeadingZeroNotAllowed),
+    invalid("CHE-000.000.000", UidErrorKind::LeadingZeroNotAllowed),
+    // Malformed input
+    invalid("ABC-109.322.551", UidErrorKind::InvalidFormat),
+    invalid("CH-109.322.551", UidErrorKind::InvalidFormat),
+    invalid("CHE-109.322.55", UidErrorKind::InvalidFormat),
+    invalid("CHE", UidErrorKind::InvalidFormat),
+];
+
+/// Main digits whose check digit would be 10, thus no valid UID exists for them.
+pub const CHECKDIGIT_TEN: &[[u8; 8]] = &[
+    [1, 0, 0, 0, 0, 0, 1, 6],
+    [1, 0, 0, 0, 0, 0, 2, 2],
+    [9, 9, 9, 9, 9, 9, 9, 8],
+];
+
+const fn valid(input: &'static str, canonical: &'static str) -> ValidVector {
+    ValidVector { input, canonical }
+}
+
+const fn invalid(input: &'static str, kind: UidErrorKind) -> InvalidVector {
+    InvalidVector { input, kind }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::uid::{calculate_checkdigit, SwissUid, UidError};
+
+    #[test]
+    fn test_valid_vectors() {
+        for v in VALID {
+            let uid = SwissUid::new(v.input);
+            assert!(uid.is_ok(), "{}: {:?}", v.input, uid);
+            assert_eq!(uid.unwrap().to_string(), v.canonical, "{}", v.input);
+        }
+    }
+
+    #[test]
+    fn test_invalid_vectors() {
+        for v in INVALID {
+            let uid = SwissUid::new(v.input);
+            assert!(uid.is_err(), "{}: {:?}", v.input, uid);
+            assert_eq!(uid.unwrap_err().kind(), v.kind, "{}", v.input);
+        }
+    }
+
+    #[test]
+    fn test_checkdigit_ten_vectors() {
+        for digits in CHECKDIGIT_TEN {
+            assert_eq!(
+                calculate_checkdigit(digits),
+                Err(UidError::InvalidCheckDigit(10, 8)),
+                "{:?}",
+                digits
+            );
+        }
+    }
+}
@@ -1,30 +1,215 @@
-use ::std::{error::Error, fmt, str::FromStr};
+use ::std::{error::Error, fmt, io, num::NonZeroU32, str::FromStr};
 
-use ::itertools::Itertools;
-
-use crate::utils::IntoNibblesNum;
+use crate::{
+    buf::SwissUidBuf,
+    explain::{explain_checkdigit, CheckDigitExplanation},
+    parse::{restore_position, sanitize_input, UidSuffix},
+};
 
 // Factors as defined in the specification
 // See: http://www.ech.ch/de/ech/ech-0097/5.2 (section 2.4.2)
-const DIGIT_FACTORS: [u8; SwissUid::NUM_CHARS_DIGITS] = [5, 4, 3, 2, 7, 6, 5, 4];
+pub(crate) const DIGIT_FACTORS: [u8; SwissUid::NUM_CHARS_DIGITS] = [5, 4, 3, 2, 7, 6, 5, 4];
+
+// Byte positions of the 9 digits in the canonical layout `CHE-109.322.551`
+pub(crate) const CANONICAL_DIGIT_POS: [usize; SwissUid::NUM_CHARS_DIGITS + 1] =
+    [4, 5, 6, 8, 9, 10, 12, 13, 14];
+
+// Precomputed products of every digit (0-9) with the factor of its position,
+// already reduced modulo 11. The checksum is then just a sum of table lookups.
+const DIGIT_PRODUCTS: [[u8; 10]; SwissUid::NUM_CHARS_DIGITS] = build_digit_products();
+
+const fn build_digit_products() -> [[u8; 10]; SwissUid::NUM_CHARS_DIGITS] {
+    let mut table = [[0u8; 10]; SwissUid::NUM_CHARS_DIGITS];
+    let mut pos = 0;
+    while pos < SwissUid::NUM_CHARS_DIGITS {
+        let mut digit = 0;
+        while digit < 10 {
+            table[pos][digit] = (DIGIT_FACTORS[pos] * digit as u8) % 11;
+            digit += 1;
+        }
+        pos += 1;
+    }
+    table
+}
 
 /// Calculates the check digit for the given 8 normal digits of the UID.
+///
+/// This is a `const fn` and can therefore be used to compute check digits at
+/// compile time.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::calculate_checkdigit;
+///
+/// const P: u8 = match calculate_checkdigit(&[1, 0, 9, 3, 2, 2, 5, 5]) {
+///     Ok(p) => p,
+///     Err(_) => panic!("invalid UID"),
+/// };
+/// assert_eq!(P, 1);
+/// ```
 #[inline]
-pub fn calculate_checkdigit(main_digits: &[u8]) -> Result<u8, UidError> {
+pub const fn calculate_checkdigit(main_digits: &[u8]) -> Result<u8, UidError> {
     if main_digits.len() != DIGIT_FACTORS.len() {
-        Err(UidError::InvalidFormat("UID must have 8 digits".to_owned()))
+        return Err(UidError::InvalidFormat(
+            "UID must have 8 digits",
+            digit_count_position(main_digits.len(), DIGIT_FACTORS.len()),
+        ));
+    }
+
+    let mut checksum = 0u8;
+    let mut i = 0;
+    while i < main_digits.len() {
+        let d = main_digits[i] as usize;
+        if d > 9 {
+            return Err(UidError::InvalidFormat(
+                "UID digits must be between 0 and 9",
+                i,
+            ));
+        }
+        checksum += DIGIT_PRODUCTS[i][d];
+        i += 1;
+    }
+    match 11 - (checksum % 11) {
+        11 => Ok(0u8),
+        10 => Err(UidError::InvalidCheckDigit(10, 8)),
+        n => Ok(n),
+    }
+}
+
+/// Checks the format and the check digit of a UID without constructing a
+/// [`SwissUid`], accepting the same input as [`SwissUid::new`].
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::{validate, UidError};
+///
+/// assert_eq!(validate("CHE-109.322.551"), Ok(()));
+/// assert_eq!(validate("CHE-010.322.557"), Err(UidError::LeadingZeroNotAllowed(4)));
+/// ```
+pub fn validate(uid: &str) -> Result<(), UidError> {
+    let s = sanitize_input(uid);
+    split_prefix(&s)
+        .and_then(|(_, rest)| {
+            scan_digits(rest)
+                .and_then(|digits| check_digits(&digits))
+                .map_err(|e| locate_digit(e, rest).offset(SwissUid::NUM_CHARS_PFX))
+        })
+        .map_err(|e| restore_position(e, uid))
+}
+
+/// Returns `true` if the UID is valid, see [`validate`].
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::is_valid;
+///
+/// assert!(is_valid("CHE-109.322.551"));
+/// assert!(!is_valid("CHE-109.322.552"));
+/// ```
+#[inline]
+pub fn is_valid(uid: &str) -> bool {
+    validate(uid).is_ok()
+}
+
+/// Splits the input into the canonical prefix and the rest.
+pub(crate) fn split_prefix(s: &str) -> Result<(UidPrefix, &str), UidError> {
+    // `get` instead of slicing, the input might be shorter than the prefix
+    // or have a multi-byte character within the first 3 bytes
+    let (Some(pfx), Some(rest)) = (
+        s.get(..SwissUid::NUM_CHARS_PFX),
+        s.get(SwissUid::NUM_CHARS_PFX..),
+    ) else {
+        return Err(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0));
+    };
+    Ok((UidPrefix::from_canonical(pfx)?, rest))
+}
+
+/// Moves the position of an error about the digit with the index of its
+/// position to the character index of that digit in `s`, or just past the end
+/// of `s` if it has fewer digits.
+fn locate_digit(e: UidError, s: &str) -> UidError {
+    let position = s
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .nth(e.position())
+        .map_or_else(|| s.chars().count(), |(i, _)| i);
+    e.at(position)
+}
+
+/// Returns the position of an error about `len` digits where `expected` are
+/// required: the first superfluous digit or the first missing one.
+const fn digit_count_position(len: usize, expected: usize) -> usize {
+    if len < expected {
+        len
     } else {
-        let checksum: u32 = DIGIT_FACTORS
-            .iter()
-            .zip_eq(main_digits.iter())
-            .map(|v| (v.0 * v.1) as u32)
-            .sum();
-        match 11 - (checksum % 11) {
-            11 => Ok(0u8),
-            10 => Err(UidError::InvalidCheckDigit(10.to_string())),
-            n => Ok(n as u8),
+        expected
+    }
+}
+
+/// Takes the first 9 ascii digits in a single pass, ignoring any separators.
+///
+/// On failure the position of the error is the number of digits found.
+fn scan_digits(s: &str) -> Result<[u8; SwissUid::NUM_CHARS_DIGITS + 1], UidError> {
+    let mut digits = [0u8; SwissUid::NUM_CHARS_DIGITS + 1];
+    let mut len = 0;
+    for b in s.bytes().filter(u8::is_ascii_digit) {
+        digits[len] = b - b'0';
+        len += 1;
+        if len == digits.len() {
+            return Ok(digits);
         }
     }
+    Err(UidError::InvalidFormat("UID must have 9 digits", len))
+}
+
+/// Verifies the number of digits, the leading digit and the check digit.
+const fn check_digits(digits: &[u8]) -> Result<(), UidError> {
+    if digits.len() != SwissUid::NUM_CHARS_DIGITS + 1 {
+        return Err(UidError::InvalidFormat(
+            "UID must have 9 digits",
+            digit_count_position(digits.len(), SwissUid::NUM_CHARS_DIGITS + 1),
+        ));
+    }
+    if digits[0] == 0 {
+        return Err(UidError::LeadingZeroNotAllowed(0));
+    }
+
+    // Get the check digit and calculate its counterpart from the first 8 digits
+    let (main, p) = digits.split_at(SwissUid::NUM_CHARS_DIGITS);
+    let p = p[0];
+    match calculate_checkdigit(main) {
+        Ok(p_calculated) if p_calculated == p => Ok(()),
+        Ok(p_calculated) => Err(UidError::MismatchedCheckDigit(
+            CheckDigitMismatch::new(p_calculated, p),
+            8,
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns the lowest 9 decimal digits of the number, most significant first.
+fn decimal_digits(mut n: u64) -> [u8; SwissUid::NUM_CHARS_DIGITS + 1] {
+    let mut digits = [0u8; SwissUid::NUM_CHARS_DIGITS + 1];
+    for d in digits.iter_mut().rev() {
+        *d = (n % 10) as u8;
+        n /= 10;
+    }
+    digits
+}
+
+/// Folds the 9 digits into their decimal number.
+const fn decimal_number(digits: &[u8]) -> u32 {
+    let mut n = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        n = n * 10 + digits[i] as u32;
+        i += 1;
+    }
+    n
 }
 
 /// A Swiss UID (Unternehmens-Identifikationsnummer) is a unique identifier for
@@ -44,17 +229,36 @@ pub fn calculate_checkdigit(main_digits: &[u8]) -> Result<u8, UidError> {
 /// let uid2: SwissUid = "CHE-109.322.551".parse().unwrap();
 /// assert_eq!(uid2.to_string().len(), 15);
 /// ```
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// The UID is packed into a single `NonZeroU32`, so `Option<SwissUid>` takes
+/// no more space than the UID itself:
+///
+/// ```rust
+/// use swiss_uid::uid::SwissUid;
+///
+/// assert_eq!(size_of::<SwissUid>(), 4);
+/// assert_eq!(size_of::<Option<SwissUid>>(), 4);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SwissUid {
-    pub(self) a: u16,
-    pub(self) b: u16,
-    pub(self) p: u16,
-    pub(self) pfx: UidPrefix,
+    /// Bits 0-29 hold the 9 digits as a decimal number (at most 999'999'999),
+    /// bit 30 is set for `CHE` and cleared for `ADM`. The leading digit is
+    /// never 0, so the value is never 0 either.
+    pub(self) repr: NonZeroU32,
 }
 
 impl SwissUid {
+    const PREFIX_CHE_BIT: u32 = 1 << 30;
+    const NUMBER_MASK: u32 = Self::PREFIX_CHE_BIT - 1;
     const NUM_CHARS_PFX: usize = 3;
     const NUM_CHARS_DIGITS: usize = 8;
+    const NUM_CHARS_CANONICAL: usize = 15;
+    const MIN_MAIN_NUMBER: u32 = 10_000_000;
+    const MAX_MAIN_NUMBER: u32 = 99_999_999;
+
+    /// Number of valid UIDs per prefix: all 90'000'000 numbers with 8 main
+    /// digits except the 8'181'818 whose check digit would be 10.
+    pub const NUM_VALID_PER_PREFIX: usize = 81_818_182;
 
     /// Creates a SwissUID from a string.
     ///
@@ -89,39 +293,683 @@ impl SwissUid {
     /// ```
     #[cfg(feature = "rand")]
     pub fn rand() -> Result<Self, UidError> {
-        use rand::Rng;
+        Self::rand_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a random valid Swiss UID using the given random number generator.
+    ///
+    /// Only the `rand_core` traits are required, so any RNG (including hardware
+    /// and `no_std` generators) can be used.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// # #[cfg(feature = "rand")]
+    /// # {
+    /// let uid = SwissUid::rand_with_rng(&mut rand::thread_rng()).unwrap();
+    /// assert_eq!(uid.to_string().len(), 15);
+    /// # }
+    /// ```
+    #[cfg(feature = "rand_core")]
+    pub fn rand_with_rng<R: rand_core::RngCore + ?Sized>(rng: &mut R) -> Result<Self, UidError> {
+        Self::rand_in_range_with_rng(rng, Self::MIN_MAIN_NUMBER..=Self::MAX_MAIN_NUMBER)
+    }
+
+    /// Generates a random valid Swiss UID whose 8 main digits, read as a number,
+    /// fall within `range`, e.g. `10_000_000..=19_999_999` for `CHE-1xx.xxx.xx`.
+    ///
+    /// Numbers in the range whose check digit would be 10 are never returned. An
+    /// error is returned if the range lies outside of `10_000_000..=99_999_999`
+    /// or contains no valid UID at all.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::rand_in_range(10_000_000..=19_999_999).unwrap();
+    /// assert!(uid.to_string().starts_with("CHE-1"));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn rand_in_range(range: ::std::ops::RangeInclusive<u32>) -> Result<Self, UidError> {
+        Self::rand_in_range_with_rng(&mut rand::thread_rng(), range)
+    }
 
-        let mut rng = rand::thread_rng();
-        let mut n = [0u8; Self::NUM_CHARS_DIGITS];
-        let mut n_iter = n.iter_mut();
+    /// Same as [`SwissUid::rand_in_range`] but using the given random number generator.
+    #[cfg(feature = "rand_core")]
+    pub fn rand_in_range_with_rng<R: rand_core::RngCore + ?Sized>(
+        rng: &mut R,
+        range: ::std::ops::RangeInclusive<u32>,
+    ) -> Result<Self, UidError> {
+        const MAX_ATTEMPTS: usize = 64;
 
-        // The first digit must be between 1 and 9
-        *n_iter.next().unwrap() = rng.gen_range(1..10);
-        // The rest can be between 0 and 9
-        for d in n_iter {
-            *d = rng.gen_range(0..10);
+        let (start, end) = (*range.start(), *range.end());
+        if start < Self::MIN_MAIN_NUMBER || end > Self::MAX_MAIN_NUMBER || start > end {
+            return Err(UidError::InvalidFormat(
+                "Range must lie within 10000000..=99999999",
+                0,
+            ));
         }
 
-        let p = calculate_checkdigit(&n).or_else(|_| {
-            // The check digit was 10 thus we change one digit and recalculate
-            if n[0] <= 1 {
-                n[0] += 1;
-            } else {
-                n[0] -= 1;
+        // Rejection sampling keeps the distribution uniform over the valid UIDs,
+        // roughly 1 in 11 numbers has the invalid check digit 10
+        let span = end - start + 1;
+        let limit = u32::MAX - u32::MAX % span;
+        for _ in 0..MAX_ATTEMPTS {
+            // Drop the biased tail of the generator's range before reducing
+            let x = rng.next_u32();
+            if x >= limit {
+                continue;
+            }
+            if let Ok(uid) = Self::from_main_number(UidPrefix::CHE, start + x % span) {
+                return Ok(uid);
             }
-            calculate_checkdigit(&n)
-        })?;
+        }
+        // Tiny ranges might not contain any valid UID at all
+        (start..=end)
+            .find_map(|n| Self::from_main_number(UidPrefix::CHE, n).ok())
+            .ok_or(UidError::InvalidCheckDigit(10, 8))
+    }
 
-        Ok(Self {
-            pfx: UidPrefix::CHE,
-            a: (&n[0..4]).into_nibbles_num(),
-            b: (&n[4..8]).into_nibbles_num(),
-            p: p as u16,
-        })
+    /// Builds the UID from its 8 main digits read as a number and calculates
+    /// the check digit.
+    pub(crate) fn from_main_number(pfx: UidPrefix, n: u32) -> Result<Self, UidError> {
+        let mut digits = [0u8; Self::NUM_CHARS_DIGITS + 1];
+        let mut rest = n;
+        for d in digits[..Self::NUM_CHARS_DIGITS].iter_mut().rev() {
+            *d = (rest % 10) as u8;
+            rest /= 10;
+        }
+        digits[Self::NUM_CHARS_DIGITS] = calculate_checkdigit(&digits[..Self::NUM_CHARS_DIGITS])?;
+        Self::from_checked_digits(pfx, &digits)
+    }
+
+    /// Returns an iterator over every valid UID with the given prefix in
+    /// ascending order, from `100.000.006` up to `999.999.996`.
+    ///
+    /// The iterator knows its exact length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::{SwissUid, UidPrefix};
+    ///
+    /// let mut all = SwissUid::iter_all(UidPrefix::CHE);
+    /// assert_eq!(all.len(), 81_818_182);
+    /// assert_eq!(all.next().unwrap().to_string(), "CHE-100.000.006");
+    /// assert_eq!(all.next().unwrap().to_string(), "CHE-100.000.012");
+    /// ```
+    pub fn iter_all(prefix: UidPrefix) -> AllUids {
+        AllUids {
+            pfx: prefix,
+            next: Self::MIN_MAIN_NUMBER,
+            remaining: Self::NUM_VALID_PER_PREFIX,
+        }
+    }
+
+    /// Parses a UID written in the canonical 15 character layout `CHE-109.322.551`.
+    ///
+    /// This is a fast path for input which is known to be mostly canonical. The
+    /// fixed layout is validated with a single pass of byte-class checks and the
+    /// digits are converted without allocating. Any input not matching the layout
+    /// falls back to the tolerant parser used by [`SwissUid::new`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::parse_canonical("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid, SwissUid::new("che 109 322 551").unwrap());
+    /// ```
+    pub fn parse_canonical(uid: &str) -> Result<Self, UidError> {
+        let Ok(bytes) = <&[u8; SwissUid::NUM_CHARS_CANONICAL]>::try_from(uid.as_bytes()) else {
+            return uid.parse();
+        };
+        let pfx = match &bytes[..Self::NUM_CHARS_PFX] {
+            b"CHE" => UidPrefix::CHE,
+            b"ADM" => UidPrefix::ADM,
+            _ => return uid.parse(),
+        };
+
+        let mut digits = [0u8; Self::NUM_CHARS_DIGITS + 1];
+        let mut invalid = (bytes[3] != b'-') | (bytes[7] != b'.') | (bytes[11] != b'.');
+        for (d, &i) in digits.iter_mut().zip(CANONICAL_DIGIT_POS.iter()) {
+            *d = bytes[i].wrapping_sub(b'0');
+            invalid |= *d > 9;
+        }
+        if invalid {
+            return uid.parse();
+        }
+
+        Self::from_checked_digits(pfx, &digits).map_err(|e| e.at(CANONICAL_DIGIT_POS[e.position()]))
+    }
+
+    /// Builds the UID from an iterator yielding exactly 9 digits, the last one
+    /// being the check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::{SwissUid, UidPrefix};
+    ///
+    /// let digits = "109322551".bytes().map(|b| b - b'0');
+    /// let uid = SwissUid::from_digit_iter(UidPrefix::CHE, digits).unwrap();
+    /// assert_eq!(uid.to_string(), "CHE-109.322.551");
+    /// assert!(SwissUid::from_digit_iter(UidPrefix::CHE, [1, 0, 9]).is_err());
+    /// ```
+    pub fn from_digit_iter<I>(prefix: UidPrefix, digits: I) -> Result<Self, UidError>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let mut buf = [0u8; Self::NUM_CHARS_DIGITS + 1];
+        let mut len = 0;
+        for d in digits {
+            if len == buf.len() {
+                return Err(UidError::InvalidFormat("UID must have 9 digits", len));
+            }
+            if d > 9 {
+                return Err(UidError::InvalidFormat(
+                    "Digits must be between 0 and 9",
+                    len,
+                ));
+            }
+            buf[len] = d;
+            len += 1;
+        }
+        if len != buf.len() {
+            return Err(UidError::InvalidFormat("UID must have 9 digits", len));
+        }
+
+        Self::from_checked_digits(prefix, &buf)
+    }
+
+    /// Parses the 9 digits following the prefix, ignoring any separators.
+    ///
+    /// The position of an error is the character index within `s`.
+    pub(crate) fn parse_digits(pfx: UidPrefix, s: &str) -> Result<Self, UidError> {
+        scan_digits(s)
+            .and_then(|digits| Self::from_checked_digits(pfx, &digits))
+            .map_err(|e| locate_digit(e, s))
+    }
+
+    /// Builds the UID from the 9 digits after verifying the leading digit and
+    /// the check digit.
+    const fn from_checked_digits(pfx: UidPrefix, digits: &[u8]) -> Result<Self, UidError> {
+        if let Err(e) = check_digits(digits) {
+            return Err(e);
+        }
+        let pfx_bit = match pfx {
+            UidPrefix::ADM => 0,
+            UidPrefix::CHE => Self::PREFIX_CHE_BIT,
+        };
+        match NonZeroU32::new(pfx_bit | decimal_number(digits)) {
+            Some(repr) => Ok(Self { repr }),
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns the 9 digits including the check digit as a decimal number.
+    const fn number(&self) -> u32 {
+        self.repr.get() & Self::NUMBER_MASK
+    }
+
+    /// Builds the UID from its 8 main digits and calculates the check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::{SwissUid, UidPrefix};
+    ///
+    /// let uid = SwissUid::from_main_digits(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 5]).unwrap();
+    /// assert_eq!(uid.to_string(), "CHE-109.322.551");
+    /// ```
+    pub const fn from_main_digits(
+        prefix: UidPrefix,
+        digits: [u8; Self::NUM_CHARS_DIGITS],
+    ) -> Result<Self, UidError> {
+        let p = match calculate_checkdigit(&digits) {
+            Ok(p) => p,
+            Err(e) => return Err(e),
+        };
+        let mut all = [p; Self::NUM_CHARS_DIGITS + 1];
+        let mut i = 0;
+        while i < digits.len() {
+            all[i] = digits[i];
+            i += 1;
+        }
+        Self::from_checked_digits(prefix, &all)
+    }
+
+    /// Builds the UID from all 9 digits, verifying the leading digit and the
+    /// check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::{SwissUid, UidPrefix};
+    ///
+    /// let uid = SwissUid::from_all_digits(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 5, 1]).unwrap();
+    /// assert_eq!(uid.to_string(), "CHE-109.322.551");
+    /// assert!(SwissUid::from_all_digits(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 5, 2]).is_err());
+    /// ```
+    pub const fn from_all_digits(
+        prefix: UidPrefix,
+        digits: [u8; Self::NUM_CHARS_DIGITS + 1],
+    ) -> Result<Self, UidError> {
+        Self::from_digits_const(prefix, digits)
+    }
+
+    /// Builds the UID from its 9 digits in a `const` context, verifying the
+    /// leading digit and the check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::{SwissUid, UidPrefix};
+    ///
+    /// const UID: SwissUid = match SwissUid::from_digits_const(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 5, 1]) {
+    ///     Ok(uid) => uid,
+    ///     Err(_) => panic!("invalid UID"),
+    /// };
+    /// assert_eq!(UID.to_string(), "CHE-109.322.551");
+    /// ```
+    pub const fn from_digits_const(
+        prefix: UidPrefix,
+        digits: [u8; Self::NUM_CHARS_DIGITS + 1],
+    ) -> Result<Self, UidError> {
+        if digits[Self::NUM_CHARS_DIGITS] > 9 {
+            return Err(UidError::InvalidFormat(
+                "UID digits must be between 0 and 9",
+                Self::NUM_CHARS_DIGITS,
+            ));
+        }
+        Self::from_checked_digits(prefix, &digits)
+    }
+
+    /// Parses a UID in a `const` context, accepting the same input as
+    /// [`SwissUid::new`] except for invisible characters, see
+    /// [`sanitize_input`]. This is what the [`uid!`](crate::uid!) macro uses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// const UID: SwissUid = match SwissUid::parse_const("CHE-109.322.551") {
+    ///     Ok(uid) => uid,
+    ///     Err(_) => panic!("invalid UID"),
+    /// };
+    /// assert_eq!(UID, SwissUid::new("CHE-109.322.551").unwrap());
+    /// ```
+    pub const fn parse_const(uid: &str) -> Result<Self, UidError> {
+        let bytes = uid.as_bytes();
+        if bytes.len() < Self::NUM_CHARS_PFX {
+            return Err(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0));
+        }
+        let pfx = match [
+            bytes[0].to_ascii_uppercase(),
+            bytes[1].to_ascii_uppercase(),
+            bytes[2].to_ascii_uppercase(),
+        ] {
+            [b'C', b'H', b'E'] => UidPrefix::CHE,
+            [b'A', b'D', b'M'] => UidPrefix::ADM,
+            _ => return Err(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0)),
+        };
+
+        // Take the first 9 ascii digits, ignoring separators like the tolerant
+        // parser, and remember their character indices for errors
+        let mut digits = [0u8; Self::NUM_CHARS_DIGITS + 1];
+        let mut positions = [0usize; Self::NUM_CHARS_DIGITS + 1];
+        let mut len = 0;
+        let mut chars = Self::NUM_CHARS_PFX;
+        let mut i = Self::NUM_CHARS_PFX;
+        while i < bytes.len() && len < digits.len() {
+            if bytes[i].is_ascii_digit() {
+                digits[len] = bytes[i] - b'0';
+                positions[len] = chars;
+                len += 1;
+            }
+            // Continuation bytes of multi-byte characters do not start a character
+            if bytes[i] & 0xc0 != 0x80 {
+                chars += 1;
+            }
+            i += 1;
+        }
+        if len != digits.len() {
+            return Err(UidError::InvalidFormat("UID must have 9 digits", chars));
+        }
+
+        match Self::from_checked_digits(pfx, &digits) {
+            Ok(uid) => Ok(uid),
+            Err(e) => Err(e.at(positions[e.position()])),
+        }
     }
 
     pub fn checkdigit(&self) -> u8 {
-        self.p as u8
+        (self.number() % 10) as u8
+    }
+
+    /// Recalculates the check digit from the 8 main digits, ignoring the
+    /// contained check digit.
+    ///
+    /// For a consistent UID this always equals [`SwissUid::checkdigit`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.recalculate_checkdigit(), Ok(uid.checkdigit()));
+    /// ```
+    pub fn recalculate_checkdigit(&self) -> Result<u8, UidError> {
+        calculate_checkdigit(&self.main_digits())
+    }
+
+    /// Re-verifies the invariants of the UID: no bits outside of the packed
+    /// prefix and number are set, it has exactly 9 digits, the first digit is
+    /// not 0 and the contained check digit matches.
+    ///
+    /// A UID created by this crate always passes. This is meant for values
+    /// which were restored from untrusted caches or memory mapped files.
+    pub fn verify(&self) -> Result<(), UidError> {
+        if self.repr.get() & !(Self::PREFIX_CHE_BIT | Self::NUMBER_MASK) != 0 {
+            return Err(UidError::InvalidFormat(
+                "Packed UID has unknown bits set",
+                0,
+            ));
+        }
+        if self.number() > 999_999_999 {
+            return Err(UidError::InvalidFormat("UID must have 9 digits", 0));
+        }
+        Self::from_checked_digits(self.prefix(), &self.digits()).map(|_| ())
+    }
+
+    /// Asserts the invariants in debug builds if the `debug-invariants`
+    /// feature is enabled.
+    #[inline(always)]
+    fn debug_check_invariants(&self) {
+        #[cfg(feature = "debug-invariants")]
+        debug_assert_eq!(self.verify(), Ok(()), "Corrupted SwissUid {:?}", self);
+    }
+
+    /// Returns the UID encoded in a single integer including its prefix.
+    ///
+    /// The layout is `prefix_code * 1_000_000_000 + digits`, where `digits` are
+    /// the 9 digits of the UID (including the check digit) and `prefix_code` is
+    /// `1` for `ADM` and `2` for `CHE`. The codes follow the alphabetical order of
+    /// the prefixes, so the integers sort like the canonical strings.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.to_u64(), 2_109_322_551);
+    /// assert_eq!(SwissUid::try_from_u64(2_109_322_551), Ok(uid));
+    /// ```
+    pub fn to_u64(&self) -> u64 {
+        self.debug_check_invariants();
+
+        let pfx_code: u64 = match self.prefix() {
+            UidPrefix::ADM => 1,
+            UidPrefix::CHE => 2,
+        };
+        self.digits()
+            .iter()
+            .fold(pfx_code, |acc, &d| acc * 10 + d as u64)
+    }
+
+    /// Decodes a UID from the integer layout produced by [`SwissUid::to_u64`],
+    /// verifying the check digit.
+    pub fn try_from_u64(n: u64) -> Result<Self, UidError> {
+        let pfx = match n / 1_000_000_000 {
+            1 => UidPrefix::ADM,
+            2 => UidPrefix::CHE,
+            _ => {
+                return Err(UidError::InvalidFormat(
+                    "Numeric UID has an unknown prefix code",
+                    0,
+                ))
+            }
+        };
+
+        Self::from_checked_digits(pfx, &decimal_digits(n % 1_000_000_000))
+    }
+
+    /// Returns the 9 digits including the check digit as a number, the same
+    /// as [`SwissUid::organisation_id`]. The prefix is not part of the number.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.as_number(), 109_322_551);
+    /// assert_eq!(u32::from(uid), 109_322_551);
+    /// assert_eq!(SwissUid::try_from(109_322_551u32), Ok(uid));
+    /// ```
+    pub fn as_number(&self) -> u32 {
+        self.organisation_id()
+    }
+
+    /// Returns `true` if the UID has the prefix `CHE`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert!(uid.is_che());
+    /// assert!(!uid.is_adm());
+    /// ```
+    pub fn is_che(&self) -> bool {
+        self.prefix() == UidPrefix::CHE
+    }
+
+    /// Returns `true` if the UID has the prefix `ADM`.
+    pub fn is_adm(&self) -> bool {
+        self.prefix() == UidPrefix::ADM
+    }
+
+    /// Returns `true` if the UID belongs to an administrative unit, which are
+    /// the UIDs with the prefix `ADM`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("ADM-109.322.551").unwrap();
+    /// assert!(uid.is_administrative());
+    /// ```
+    pub fn is_administrative(&self) -> bool {
+        self.is_adm()
+    }
+
+    /// Returns the UID category as used by the eCH-0097 XML element
+    /// `uidOrganisationIdCategorie` (`"CHE"` or `"ADM"`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("ADM-109.322.551").unwrap();
+    /// assert_eq!(uid.category_code(), "ADM");
+    /// ```
+    pub fn category_code(&self) -> &'static str {
+        self.prefix().as_str()
+    }
+
+    /// Returns the number as used by the eCH-0097 XML element `uidOrganisationId`,
+    /// which are the 9 digits of the UID including the check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.organisation_id(), 109_322_551);
+    /// ```
+    pub fn organisation_id(&self) -> u32 {
+        self.number()
+    }
+
+    /// Returns the intermediate steps of the check digit calculation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.explain_checkdigit().sum(), 109);
+    /// ```
+    pub fn explain_checkdigit(&self) -> CheckDigitExplanation {
+        explain_checkdigit(&self.main_digits())
+            .expect("SwissUid always contains 8 valid main digits")
+    }
+
+    /// Returns the packed representation as one integer which sorts like
+    /// [`SwissUid::to_u64`]: the `CHE` bit sorts above every `ADM` number.
+    pub(crate) fn packed_key(&self) -> u64 {
+        self.repr.get() as u64
+    }
+
+    /// Returns the prefix of the UID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::{SwissUid, UidPrefix};
+    ///
+    /// let uid = SwissUid::new("ADM-100.000.029").unwrap();
+    /// assert_eq!(uid.prefix(), UidPrefix::ADM);
+    /// ```
+    pub fn prefix(&self) -> UidPrefix {
+        if self.repr.get() & Self::PREFIX_CHE_BIT != 0 {
+            UidPrefix::CHE
+        } else {
+            UidPrefix::ADM
+        }
+    }
+
+    /// Returns the 8 main digits without the check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.main_digits(), [1, 0, 9, 3, 2, 2, 5, 5]);
+    /// ```
+    pub fn main_digits(&self) -> [u8; Self::NUM_CHARS_DIGITS] {
+        let mut digits = [0u8; Self::NUM_CHARS_DIGITS];
+        digits.copy_from_slice(&self.digits()[..Self::NUM_CHARS_DIGITS]);
+        digits
+    }
+
+    /// Returns all 9 digits including the check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.digits(), [1, 0, 9, 3, 2, 2, 5, 5, 1]);
+    /// ```
+    pub fn digits(&self) -> [u8; Self::NUM_CHARS_DIGITS + 1] {
+        decimal_digits(self.number() as u64)
+    }
+
+    /// Returns an iterator over all 9 digits, ending with the check digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// let digits: Vec<u8> = uid.iter_digits().collect();
+    /// assert_eq!(digits, [1, 0, 9, 3, 2, 2, 5, 5, 1]);
+    /// ```
+    pub fn iter_digits(&self) -> ::std::array::IntoIter<u8, { Self::NUM_CHARS_DIGITS + 1 }> {
+        self.digits().into_iter()
+    }
+
+    /// Writes the canonical representation directly into `w` without an
+    /// intermediate `String` and returns the number of bytes written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// let mut out = Vec::new();
+    /// assert_eq!(uid.write_to(&mut out).unwrap(), 15);
+    /// assert_eq!(out, b"CHE-109.322.551");
+    /// ```
+    pub fn write_to<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<usize> {
+        let buf = SwissUidBuf::new(*self);
+        w.write_all(buf.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    /// Same as [`SwissUid::write_to`] but writing into a `fmt::Write`, such as
+    /// a `String` or a fixed-capacity string of an embedded target.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// let mut out = String::from("UID: ");
+    /// uid.write_fmt_to(&mut out).unwrap();
+    /// assert_eq!(out, "UID: CHE-109.322.551");
+    /// ```
+    pub fn write_fmt_to<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        w.write_str(SwissUidBuf::new(*self).as_str())
+    }
+
+    /// Returns the canonical representation as ASCII bytes on the stack.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(&uid.to_array(), b"CHE-109.322.551");
+    /// ```
+    pub fn to_array(&self) -> [u8; Self::NUM_CHARS_CANONICAL] {
+        let mut out = [0u8; Self::NUM_CHARS_CANONICAL];
+        out.copy_from_slice(SwissUidBuf::new(*self).as_bytes());
+        out
+    }
+
+    /// Returns the canonical representation in a stack-allocated buffer which
+    /// derefs to `str`, without allocating a `String`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.as_str_buf().as_str(), "CHE-109.322.551");
+    /// ```
+    pub fn as_str_buf(&self) -> SwissUidBuf {
+        SwissUidBuf::new(*self)
     }
 
     /// Returns the UID as a string with the suffix " MWST" (Mehrwertsteuer).
@@ -135,7 +983,7 @@ impl SwissUid {
     /// assert_eq!(uid.to_string_mwst(), "CHE-109.322.551 MWST".to_owned());
     /// ```
     pub fn to_string_mwst(&self) -> String {
-        format!("{} MWST", self)
+        self.to_string_with_suffix(UidSuffix::MWST)
     }
 
     /// Returns the UID as a string with the suffix " HR" (Handelsregister).
@@ -149,122 +997,393 @@ impl SwissUid {
     /// assert_eq!(uid.to_string_hr(), "CHE-109.322.551 HR".to_owned());
     /// ```
     pub fn to_string_hr(&self) -> String {
-        format!("{} HR", self)
+        self.to_string_with_suffix(UidSuffix::HR)
+    }
+
+    /// Returns the UID as a string with the suffix " TVA" (taxe sur la valeur ajoutée).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.to_string_tva(), "CHE-109.322.551 TVA".to_owned());
+    /// ```
+    pub fn to_string_tva(&self) -> String {
+        self.to_string_with_suffix(UidSuffix::TVA)
+    }
+
+    /// Returns the UID as a string with the suffix " IVA" (imposta sul valore aggiunto).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.to_string_iva(), "CHE-109.322.551 IVA".to_owned());
+    /// ```
+    pub fn to_string_iva(&self) -> String {
+        self.to_string_with_suffix(UidSuffix::IVA)
+    }
+
+    /// Returns the UID as a string with the suffix " VAT" (value added tax).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.to_string_vat(), "CHE-109.322.551 VAT".to_owned());
+    /// ```
+    pub fn to_string_vat(&self) -> String {
+        self.to_string_with_suffix(UidSuffix::VAT)
+    }
+
+    /// Returns the UID as a string followed by a space and the given suffix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::parse::UidSuffix;
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    /// assert_eq!(uid.to_string_with_suffix(UidSuffix::RC), "CHE-109.322.551 RC");
+    /// ```
+    pub fn to_string_with_suffix(&self, suffix: UidSuffix) -> String {
+        format!("{} {}", self, suffix)
     }
 }
 
 impl FromStr for SwissUid {
     type Err = UidError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let pfx: UidPrefix = s[..Self::NUM_CHARS_PFX].parse()?;
-
-        // Parse the 9 ascii digits
-        let digits: Vec<u8> = s
-            .chars()
-            .skip(Self::NUM_CHARS_PFX)
-            .filter(|c| c.is_ascii_digit())
-            .take(Self::NUM_CHARS_DIGITS + 1)
-            .filter_map(|c| c.to_digit(10).map(|d| d as u8))
-            .collect();
-        if digits.len() != Self::NUM_CHARS_DIGITS + 1 {
-            return Err(UidError::InvalidFormat("UID must have 9 digits".to_owned()));
-        }
-        if digits[0] == 0 {
-            return Err(UidError::LeadingZeroNotAllowed);
-        }
-
-        // Get the check digit and calculate its counterpart from the first 8 digits
-        let p = digits[Self::NUM_CHARS_DIGITS];
-        calculate_checkdigit(&digits[..Self::NUM_CHARS_DIGITS]).and_then(|p_calculated| {
-            if p_calculated == p {
-                Ok(Self {
-                    pfx,
-                    a: (&digits[0..4]).into_nibbles_num(),
-                    b: (&digits[4..8]).into_nibbles_num(),
-                    p: p as u16,
-                })
-            } else {
-                Err(UidError::MismatchedCheckDigit(format!(
-                    "Calculated check digit is [{}]",
-                    p_calculated
-                )))
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let s = sanitize_input(input);
+        split_prefix(&s)
+            .and_then(|(pfx, rest)| {
+                Self::parse_digits(pfx, rest).map_err(|e| e.offset(Self::NUM_CHARS_PFX))
+            })
+            .map_err(|e| restore_position(e, input))
+    }
+}
+
+/// Iterator over every valid UID of a prefix, see [`SwissUid::iter_all`].
+#[derive(Debug, Clone)]
+pub struct AllUids {
+    pfx: UidPrefix,
+    next: u32,
+    remaining: usize,
+}
+
+impl Iterator for AllUids {
+    type Item = SwissUid;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next <= SwissUid::MAX_MAIN_NUMBER {
+            let n = self.next;
+            self.next += 1;
+            if let Ok(uid) = SwissUid::from_main_number(self.pfx, n) {
+                self.remaining -= 1;
+                return Some(uid);
             }
-        })
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for AllUids {}
+
+impl ::std::iter::FusedIterator for AllUids {}
+
+impl TryFrom<u64> for SwissUid {
+    type Error = UidError;
+
+    fn try_from(n: u64) -> Result<Self, Self::Error> {
+        Self::try_from_u64(n)
+    }
+}
+
+impl From<SwissUid> for u64 {
+    fn from(uid: SwissUid) -> Self {
+        uid.to_u64()
     }
 }
 
+/// Interprets the number as the 9 digits of a `CHE` UID including the check
+/// digit, the inverse of [`SwissUid::as_number`] for `CHE` UIDs.
+impl TryFrom<u32> for SwissUid {
+    type Error = UidError;
+
+    fn try_from(n: u32) -> Result<Self, Self::Error> {
+        if n > 999_999_999 {
+            return Err(UidError::InvalidFormat("UID must have 9 digits", 0));
+        }
+        Self::from_checked_digits(UidPrefix::CHE, &decimal_digits(n as u64))
+    }
+}
+
+impl From<SwissUid> for u32 {
+    fn from(uid: SwissUid) -> Self {
+        uid.as_number()
+    }
+}
+
+/// The `Debug` output is the compact `CHE-109.322.55[1]` with the check digit in
+/// brackets. The alternate form `{:#?}` uses the standard struct shape instead.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::SwissUid;
+///
+/// let uid = SwissUid::new("CHE-109.322.551").unwrap();
+/// assert_eq!(format!("{:?}", uid), "CHE-109.322.55[1]");
+/// assert_eq!(
+///     format!("{:#?}", uid),
+///     "SwissUid {\n    prefix: CHE,\n    digits: 10932255,\n    check_digit: 1,\n}"
+/// );
+/// ```
 impl fmt::Debug for SwissUid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let a012 = self.a >> 4;
-        let a3 = self.a & 0x000f;
-
-        let b01 = (self.b & 0xff00) >> 8;
-        let b23 = self.b & 0x00ff;
+        if f.alternate() {
+            return f
+                .debug_struct("SwissUid")
+                .field("prefix", &self.prefix())
+                .field("digits", &(self.number() / 10))
+                .field("check_digit", &self.checkdigit())
+                .finish();
+        }
 
+        let n = self.number();
         write!(
             f,
-            "{}-{:03x}.{:x}{:02x}.{:02x}[{}]",
-            self.pfx, a012, a3, b01, b23, self.p
+            "{}-{:03}.{:03}.{:02}[{}]",
+            self.prefix(),
+            n / 1_000_000,
+            n / 1_000 % 1_000,
+            n / 10 % 100,
+            n % 10
         )
     }
 }
 
 impl fmt::Display for SwissUid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let a012 = self.a >> 4;
-        let a3 = self.a & 0x000f;
+        self.debug_check_invariants();
 
-        let b01 = (self.b & 0xff00) >> 8;
-        let b23 = self.b & 0x00ff;
+        if f.alternate() {
+            return write!(f, "{}{:09}", self.prefix(), self.number());
+        }
+        f.write_str(SwissUidBuf::new(*self).as_str())
+    }
+}
 
-        write!(
-            f,
-            "{}-{:03x}.{:x}{:02x}.{:02x}{}",
-            self.pfx, a012, a3, b01, b23, self.p
-        )
+/// UIDs are ordered by prefix and then by number, which is the same order as
+/// the one of their canonical strings.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::SwissUid;
+///
+/// let adm = SwissUid::new("ADM-999.999.996").unwrap();
+/// let che = SwissUid::new("CHE-100.000.006").unwrap();
+/// assert!(adm < che);
+/// ```
+impl Ord for SwissUid {
+    fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+        self.packed_key().cmp(&other.packed_key())
+    }
+}
+
+impl PartialOrd for SwissUid {
+    fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 unsafe impl Send for SwissUid {}
 unsafe impl Sync for SwissUid {}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UidPrefix {
     CHE,
     ADM,
 }
 
+impl UidPrefix {
+    /// All prefixes in the order of their integer encoding.
+    pub const ALL: [UidPrefix; 2] = [UidPrefix::ADM, UidPrefix::CHE];
+
+    /// Prefixes which are accepted by `UidPrefix::from_str` besides the
+    /// canonical ones.
+    ///
+    /// `IDE` and `IDI` are the French and Italian abbreviations of the UID and
+    /// both denote a `CHE` number. Parsing a whole UID only accepts them if
+    /// enabled in the [`ParseOptions`](crate::parse::ParseOptions).
+    pub const ALIASES: [(&'static str, UidPrefix); 2] =
+        [("IDE", UidPrefix::CHE), ("IDI", UidPrefix::CHE)];
+
+    /// Returns the prefix as it is written in the canonical form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::UidPrefix;
+    ///
+    /// assert_eq!(UidPrefix::ADM.as_str(), "ADM");
+    /// assert_eq!("ide".parse::<UidPrefix>().unwrap().as_str(), "CHE");
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            UidPrefix::CHE => "CHE",
+            UidPrefix::ADM => "ADM",
+        }
+    }
+
+    /// Parses one of the canonical prefixes, rejecting the aliases.
+    pub(crate) fn from_canonical(s: &str) -> Result<Self, UidError> {
+        Self::ALL
+            .into_iter()
+            .find(|pfx| pfx.as_str().eq_ignore_ascii_case(s))
+            .ok_or(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0))
+    }
+}
+
 impl FromStr for UidPrefix {
     type Err = UidError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_uppercase().as_str() {
-            "CHE" => Ok(UidPrefix::CHE),
-            "ADM" => Ok(UidPrefix::ADM),
-            _ => Err(UidError::InvalidFormat(
-                "Prefix must be 'CHE' or 'ADM'".to_owned(),
-            )),
-        }
+        Self::ALL
+            .iter()
+            .map(|&pfx| (pfx.as_str(), pfx))
+            .chain(Self::ALIASES)
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+            .map(|(_, pfx)| pfx)
+            .ok_or(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0))
+    }
+}
+
+/// Prefixes are ordered alphabetically, `ADM` before `CHE`.
+impl Ord for UidPrefix {
+    fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for UidPrefix {
+    fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 impl fmt::Display for UidPrefix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        f.pad(self.as_str())
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Errors returned when parsing or validating a Swiss UID.
+///
+/// The variants only carry static or copyable data, so creating an error never
+/// allocates. The human readable message is built lazily by the `Display` impl.
+///
+/// The last field of every variant is the position where the error was
+/// detected, see [`UidError::position`]. Errors are only equal if their
+/// positions are equal as well, use [`UidError::kind`] to compare the reason
+/// only.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::SwissUid;
+///
+/// let a = SwissUid::new("CHE-109.322.552").unwrap_err();
+/// let b = SwissUid::new("CHE109322552").unwrap_err();
+/// assert_ne!(a, b);
+/// assert_eq!(a.kind(), b.kind());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UidError {
     /// Malformed Swiss UID string format
-    InvalidFormat(String),
+    InvalidFormat(&'static str, usize),
     /// Leading zero is not allowed in the UID
-    LeadingZeroNotAllowed,
+    LeadingZeroNotAllowed(usize),
     /// The calculated check digit is in the invalid range, no UID can have this check digit
-    InvalidCheckDigit(String),
+    InvalidCheckDigit(u8, usize),
     /// The calculated check digit of the first 8 digits does not match the given 9th digit (right)
-    MismatchedCheckDigit(String),
+    MismatchedCheckDigit(CheckDigitMismatch, usize),
+}
+
+impl UidError {
+    /// Returns the kind of the error without any of its payload.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::{SwissUid, UidErrorKind};
+    ///
+    /// let err = SwissUid::new("CHE-109.322.552").unwrap_err();
+    /// assert_eq!(err.kind(), UidErrorKind::MismatchedCheckDigit);
+    /// ```
+    pub fn kind(&self) -> UidErrorKind {
+        match self {
+            UidError::InvalidFormat(_, _) => UidErrorKind::InvalidFormat,
+            UidError::LeadingZeroNotAllowed(_) => UidErrorKind::LeadingZeroNotAllowed,
+            UidError::InvalidCheckDigit(_, _) => UidErrorKind::InvalidCheckDigit,
+            UidError::MismatchedCheckDigit(_, _) => UidErrorKind::MismatchedCheckDigit,
+        }
+    }
+
+    /// Returns the index of the character in the parsed input where the error
+    /// was detected, e.g. to place a cursor in an editor.
+    ///
+    /// Errors about missing characters point just past the end of the input.
+    /// Errors of functions taking digits instead of a string refer to the
+    /// index of the digit, errors unrelated to any position carry 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use swiss_uid::uid::SwissUid;
+    ///
+    /// assert_eq!(SwissUid::new("CHE-010.322.557").unwrap_err().position(), 4);
+    /// assert_eq!(SwissUid::new("CHE-109.322.552").unwrap_err().position(), 14);
+    /// assert_eq!(SwissUid::new("CHE-109.322").unwrap_err().position(), 11);
+    /// ```
+    pub const fn position(&self) -> usize {
+        match *self {
+            UidError::InvalidFormat(_, position)
+            | UidError::LeadingZeroNotAllowed(position)
+            | UidError::InvalidCheckDigit(_, position)
+            | UidError::MismatchedCheckDigit(_, position) => position,
+        }
+    }
+
+    /// Returns the error with its position replaced.
+    pub(crate) const fn at(self, position: usize) -> Self {
+        match self {
+            UidError::InvalidFormat(s, _) => UidError::InvalidFormat(s, position),
+            UidError::LeadingZeroNotAllowed(_) => UidError::LeadingZeroNotAllowed(position),
+            UidError::InvalidCheckDigit(p, _) => UidError::InvalidCheckDigit(p, position),
+            UidError::MismatchedCheckDigit(m, _) => UidError::MismatchedCheckDigit(m, position),
+        }
+    }
+
+    /// Returns the error with its position moved `n` characters to the right,
+    /// for errors of a part of the input starting at character `n`.
+    pub(crate) const fn offset(self, n: usize) -> Self {
+        let position = self.position();
+        self.at(position + n)
+    }
 }
 
 impl Error for UidError {}
@@ -272,14 +1391,65 @@ impl Error for UidError {}
 impl fmt::Display for UidError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            UidError::InvalidFormat(s) => write!(f, "Invalid format: {}", s),
-            UidError::LeadingZeroNotAllowed => write!(f, "Leading zero is not allowed"),
-            UidError::InvalidCheckDigit(s) => write!(f, "Invalid check digit: {}", s),
-            UidError::MismatchedCheckDigit(s) => write!(f, "Mismatched check digit: {}", s),
+            UidError::InvalidFormat(s, _) => write!(f, "Invalid format: {}", s),
+            UidError::LeadingZeroNotAllowed(_) => write!(f, "Leading zero is not allowed"),
+            UidError::InvalidCheckDigit(s, _) => write!(f, "Invalid check digit: {}", s),
+            UidError::MismatchedCheckDigit(m, _) => write!(
+                f,
+                "Mismatched check digit: Calculated check digit is [{}]",
+                m.expected()
+            ),
         }
     }
 }
 
+/// Details of a [`UidError::MismatchedCheckDigit`] error.
+///
+/// # Example
+///
+/// ```rust
+/// use swiss_uid::uid::{SwissUid, UidError};
+///
+/// match SwissUid::new("CHE-109.322.552") {
+///     Err(UidError::MismatchedCheckDigit(m, _)) => {
+///         assert_eq!(m.expected(), 1);
+///         assert_eq!(m.found(), 2);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckDigitMismatch {
+    expected: u8,
+    found: u8,
+}
+
+impl CheckDigitMismatch {
+    pub(crate) const fn new(expected: u8, found: u8) -> Self {
+        Self { expected, found }
+    }
+
+    /// Returns the check digit calculated from the first 8 digits.
+    pub fn expected(&self) -> u8 {
+        self.expected
+    }
+
+    /// Returns the check digit contained in the input.
+    pub fn found(&self) -> u8 {
+        self.found
+    }
+}
+
+/// The kind of a [`UidError`], a plain copyable tag suitable for counting and
+/// matching on hot paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UidErrorKind {
+    InvalidFormat,
+    LeadingZeroNotAllowed,
+    InvalidCheckDigit,
+    MismatchedCheckDigit,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -287,38 +1457,65 @@ mod test {
     #[test]
     fn test_valid_uid_che() {
         let uid = SwissUid::new("CHE-109.322.551");
-        assert_eq!(uid.is_ok(), true);
+        assert!(uid.is_ok());
         let uid = uid.unwrap();
-        assert_eq!(uid.pfx, UidPrefix::CHE);
-        assert_eq!(uid.a, 0x1093);
-        assert_eq!(uid.b, 0x2255);
-        assert_eq!(uid.p, 1);
+        assert_eq!(uid.prefix(), UidPrefix::CHE);
+        assert_eq!(uid.number(), 109_322_551);
+        assert_eq!(uid.checkdigit(), 1);
         assert_eq!(uid.to_string(), "CHE-109.322.551");
         assert_eq!(uid.to_string_hr(), "CHE-109.322.551 HR");
         assert_eq!(uid.to_string_mwst(), "CHE-109.322.551 MWST");
         assert_eq!(format!("{}", uid), "CHE-109.322.551");
         assert_eq!(format!("{:?}", uid), "CHE-109.322.55[1]");
+        assert_eq!(
+            format!("{:#?}", uid),
+            "SwissUid {\n    prefix: CHE,\n    digits: 10932255,\n    check_digit: 1,\n}"
+        );
     }
 
     #[test]
     fn test_valid_uid_parse() {
         let uid: SwissUid = "CHE-109.322.551".parse().unwrap();
-        assert_eq!(uid.pfx, UidPrefix::CHE);
-        assert_eq!(uid.a, 0x1093);
-        assert_eq!(uid.b, 0x2255);
-        assert_eq!(uid.p, 1);
+        assert_eq!(uid.prefix(), UidPrefix::CHE);
+        assert_eq!(uid.number(), 109_322_551);
+        assert_eq!(uid.checkdigit(), 1);
+        assert_eq!(uid.to_string(), "CHE-109.322.551");
+    }
+
+    #[test]
+    fn test_parse_canonical() {
+        let uid = SwissUid::parse_canonical("CHE-109.322.551").unwrap();
+        assert_eq!(uid, SwissUid::new("CHE-109.322.551").unwrap());
+        let uid = SwissUid::parse_canonical("ADM-100.002.005").unwrap();
+        assert_eq!(uid.to_string(), "ADM-100.002.005");
+
+        // Non canonical input falls back to the tolerant parser
+        let uid = SwissUid::parse_canonical("che109322551").unwrap();
+        assert_eq!(uid.to_string(), "CHE-109.322.551");
+        let uid = SwissUid::parse_canonical("CHE 109 322 551").unwrap();
         assert_eq!(uid.to_string(), "CHE-109.322.551");
+
+        assert_eq!(
+            SwissUid::parse_canonical("CHE-109.322.552"),
+            Err(UidError::MismatchedCheckDigit(
+                CheckDigitMismatch::new(1, 2),
+                14
+            ))
+        );
+        assert_eq!(
+            SwissUid::parse_canonical("CHE-010.322.557"),
+            Err(UidError::LeadingZeroNotAllowed(4))
+        );
     }
 
     #[test]
     fn test_valid_uid_with_zeroes() {
         let uid = SwissUid::new("CHE-100.002.005");
-        assert_eq!(uid.is_ok(), true);
+        assert!(uid.is_ok());
         let uid = uid.unwrap();
-        assert_eq!(uid.pfx, UidPrefix::CHE);
-        assert_eq!(uid.a, 0x1000);
-        assert_eq!(uid.b, 0x0200);
-        assert_eq!(uid.p, 5);
+        assert_eq!(uid.prefix(), UidPrefix::CHE);
+        assert_eq!(uid.number(), 100_002_005);
+        assert_eq!(uid.checkdigit(), 5);
         assert_eq!(uid.to_string(), "CHE-100.002.005");
     }
 
@@ -326,24 +1523,97 @@ mod test {
     #[test]
     fn test_valid_uid_rand() {
         let uid = SwissUid::rand();
-        assert_eq!(uid.is_ok(), true);
+        assert!(uid.is_ok());
         let uid = uid.unwrap();
-        assert_eq!(uid.pfx, UidPrefix::CHE);
+        assert_eq!(uid.prefix(), UidPrefix::CHE);
         assert_eq!(uid.to_string().len(), 15, "{}", uid);
     }
 
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_valid_uid_rand_in_range() {
+        for _ in 0..100 {
+            let uid = SwissUid::rand_in_range(10_000_000..=10_009_999).unwrap();
+            assert!(uid.to_string().starts_with("CHE-100.0"), "{}", uid);
+        }
+
+        // The only number in the range has the check digit 10
+        assert_eq!(
+            SwissUid::rand_in_range(10_000_016..=10_000_016),
+            Err(UidError::InvalidCheckDigit(10, 8))
+        );
+        let uid = SwissUid::rand_in_range(10_000_016..=10_000_017).unwrap();
+        assert_eq!(uid.to_string(), "CHE-100.000.176");
+
+        assert!(SwissUid::rand_in_range(0..=9_999_999).is_err());
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 20_000_000..=10_000_000;
+        assert!(SwissUid::rand_in_range(empty).is_err());
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn test_valid_uid_rand_with_rng() {
+        // A simple deterministic generator only implementing `RngCore`
+        struct Counter(u32);
+        impl rand_core::RngCore for Counter {
+            fn next_u32(&mut self) -> u32 {
+                self.0 = self.0.wrapping_add(1);
+                self.0
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.next_u32() as u64
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        let mut rng = Counter(0);
+        let uid = SwissUid::rand_with_rng(&mut rng).unwrap();
+        assert_eq!(uid.to_string(), "CHE-100.000.012");
+        let uid = SwissUid::rand_in_range_with_rng(&mut rng, 20_000_000..=29_999_999).unwrap();
+        assert_eq!(uid.to_string(), "CHE-200.000.024");
+    }
+
     #[test]
     fn test_valid_uid_adm() {
         let uid = SwissUid::new("ADM-109.322.551");
-        assert_eq!(uid.is_ok(), true);
+        assert!(uid.is_ok());
         let uid = uid.unwrap();
         assert_eq!(uid.to_string(), "ADM-109.322.551");
     }
 
+    #[test]
+    fn test_prefix_predicates() {
+        let che = SwissUid::new("CHE-109.322.551").unwrap();
+        assert!(che.is_che() && !che.is_adm() && !che.is_administrative());
+        let adm = SwissUid::new("ADM-109.322.551").unwrap();
+        assert!(!adm.is_che() && adm.is_adm() && adm.is_administrative());
+    }
+
+    #[test]
+    fn test_prefix_aliases() {
+        assert_eq!("IDE".parse(), Ok(UidPrefix::CHE));
+        assert_eq!("idi".parse(), Ok(UidPrefix::CHE));
+        assert_eq!(
+            SwissUid::new("IDE-109.322.551"),
+            Err(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0))
+        );
+        for pfx in UidPrefix::ALL {
+            assert_eq!(pfx.as_str().parse(), Ok(pfx));
+            assert_eq!(format!("{:>4}", pfx), format!(" {}", pfx.as_str()));
+        }
+    }
+
     #[test]
     fn test_incomplete_prefix() {
         let uid = SwissUid::new("CH-109.322.552");
-        assert_eq!(uid.is_err(), true);
+        assert!(uid.is_err());
         let uid = uid.unwrap_err();
         assert_eq!(
             format!("{}", uid),
@@ -351,10 +1621,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_short_and_multibyte_prefix() {
+        for input in [
+            "",
+            "C",
+            "CH",
+            "ü",
+            "CHü-109.322.551",
+            "€HE-109.322.551",
+            "Cü",
+        ] {
+            assert_eq!(
+                SwissUid::new(input),
+                Err(UidError::InvalidFormat("Prefix must be 'CHE' or 'ADM'", 0)),
+                "{}",
+                input
+            );
+        }
+    }
+
     #[test]
     fn test_unknown_prefix() {
         let uid = SwissUid::new("ABC-109.322.551");
-        assert_eq!(uid.is_err(), true);
+        assert!(uid.is_err());
         let uid = uid.unwrap_err();
         assert_eq!(
             format!("{}", uid),
@@ -365,26 +1655,57 @@ mod test {
     #[test]
     fn test_leading_zero_not_allowed() {
         let uid = SwissUid::new("CHE-010.322.557");
-        assert_eq!(uid.is_err(), true, "{:?}", uid);
+        assert!(uid.is_err(), "{:?}", uid);
         let uid = uid.unwrap_err();
-        assert_eq!(format!("{:?}", uid), "LeadingZeroNotAllowed");
+        assert_eq!(format!("{:?}", uid), "LeadingZeroNotAllowed(4)");
     }
 
     #[test]
     fn test_invalid_checkdigit() {
         let uid = SwissUid::new("CHE-100.002.000");
-        assert_eq!(uid.is_err(), true);
+        assert!(uid.is_err());
         let uid = uid.unwrap_err();
         assert_eq!(
             format!("{:?}", uid),
-            "MismatchedCheckDigit(\"Calculated check digit is [5]\")"
+            "MismatchedCheckDigit(CheckDigitMismatch { expected: 5, found: 0 }, 14)"
+        );
+    }
+
+    #[test]
+    fn test_checkdigit_ten_is_invalid() {
+        let uid = SwissUid::new("CHE-100.000.160");
+        assert!(uid.is_err());
+        let uid = uid.unwrap_err();
+        assert_eq!(uid, UidError::InvalidCheckDigit(10, 14));
+        assert_eq!(uid.kind(), UidErrorKind::InvalidCheckDigit);
+        assert_eq!(format!("{}", uid), "Invalid check digit: 10");
+    }
+
+    #[test]
+    fn test_calculate_checkdigit() {
+        assert_eq!(calculate_checkdigit(&[1, 0, 9, 3, 2, 2, 5, 5]), Ok(1));
+        assert_eq!(calculate_checkdigit(&[1, 0, 0, 0, 0, 2, 0, 0]), Ok(5));
+        assert_eq!(
+            calculate_checkdigit(&[1, 0, 0, 0, 0, 0, 1, 6]),
+            Err(UidError::InvalidCheckDigit(10, 8))
+        );
+        assert_eq!(
+            calculate_checkdigit(&[1, 0, 9, 3, 2, 2, 5]),
+            Err(UidError::InvalidFormat("UID must have 8 digits", 7))
+        );
+        assert_eq!(
+            calculate_checkdigit(&[1, 0, 9, 3, 2, 2, 5, 10]),
+            Err(UidError::InvalidFormat(
+                "UID digits must be between 0 and 9",
+                7
+            ))
         );
     }
 
     #[test]
     fn test_mismatched_checkdigit() {
         let uid = SwissUid::new("CHE-109.322.552");
-        assert_eq!(uid.is_err(), true);
+        assert!(uid.is_err());
         let uid = uid.unwrap_err();
         assert_eq!(
             format!("{}", uid),
@@ -392,21 +1713,297 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_ech_fields() {
+        let uid = SwissUid::new("CHE-100.002.005").unwrap();
+        assert_eq!(uid.category_code(), "CHE");
+        assert_eq!(uid.organisation_id(), 100_002_005);
+    }
+
+    #[test]
+    fn test_u64_roundtrip() {
+        let uid = SwissUid::new("CHE-109.322.551").unwrap();
+        assert_eq!(uid.to_u64(), 2_109_322_551);
+        assert_eq!(u64::from(uid), 2_109_322_551);
+        assert_eq!(SwissUid::try_from(2_109_322_551u64), Ok(uid));
+
+        let uid = SwissUid::new("ADM-100.002.005").unwrap();
+        assert_eq!(uid.to_u64(), 1_100_002_005);
+        assert_eq!(SwissUid::try_from_u64(uid.to_u64()), Ok(uid));
+    }
+
+    #[test]
+    fn test_u64_invalid() {
+        assert_eq!(
+            SwissUid::try_from_u64(109_322_551),
+            Err(UidError::InvalidFormat(
+                "Numeric UID has an unknown prefix code",
+                0
+            ))
+        );
+        assert_eq!(
+            SwissUid::try_from_u64(2_109_322_552),
+            Err(UidError::MismatchedCheckDigit(
+                CheckDigitMismatch::new(1, 2),
+                8
+            ))
+        );
+        assert_eq!(
+            SwissUid::try_from_u64(2_010_322_557),
+            Err(UidError::LeadingZeroNotAllowed(0))
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let uid = SwissUid::new("CHE-109.322.551").unwrap();
+        assert_eq!(uid.recalculate_checkdigit(), Ok(1));
+        assert_eq!(uid.verify(), Ok(()));
+
+        let corrupted = SwissUid {
+            repr: NonZeroU32::new(uid.repr.get() + 1).unwrap(),
+        };
+        assert_eq!(corrupted.recalculate_checkdigit(), Ok(1));
+        assert_eq!(
+            corrupted.verify(),
+            Err(UidError::MismatchedCheckDigit(
+                CheckDigitMismatch::new(1, 2),
+                8
+            ))
+        );
+
+        let corrupted = SwissUid {
+            repr: NonZeroU32::new(uid.repr.get() | SwissUid::NUMBER_MASK).unwrap(),
+        };
+        assert_eq!(
+            corrupted.verify(),
+            Err(UidError::InvalidFormat("UID must have 9 digits", 0))
+        );
+
+        let corrupted = SwissUid {
+            repr: NonZeroU32::new(uid.repr.get() | 1 << 31).unwrap(),
+        };
+        assert_eq!(
+            corrupted.verify(),
+            Err(UidError::InvalidFormat(
+                "Packed UID has unknown bits set",
+                0
+            ))
+        );
+
+        let corrupted = SwissUid {
+            repr: NonZeroU32::new(uid.repr.get() - 100_000_000).unwrap(),
+        };
+        assert_eq!(corrupted.verify(), Err(UidError::LeadingZeroNotAllowed(0)));
+    }
+
+    #[test]
+    fn test_allocation_free_formatting() {
+        for uid in SwissUid::iter_all(UidPrefix::ADM).step_by(7919).take(100) {
+            let expected = uid.to_string();
+            let mut out = String::new();
+            uid.write_fmt_to(&mut out).unwrap();
+            assert_eq!(out, expected);
+            assert_eq!(&uid.to_array()[..], expected.as_bytes());
+            assert_eq!(uid.as_str_buf().as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn test_iter_all() {
+        let mut all = SwissUid::iter_all(UidPrefix::ADM);
+        assert_eq!(all.len(), SwissUid::NUM_VALID_PER_PREFIX);
+        let first: Vec<String> = all.by_ref().take(3).map(|uid| uid.to_string()).collect();
+        assert_eq!(
+            first,
+            ["ADM-100.000.006", "ADM-100.000.012", "ADM-100.000.029"]
+        );
+        assert_eq!(all.len(), SwissUid::NUM_VALID_PER_PREFIX - 3);
+
+        // Skip ahead to the end of the number space, 3 of the last 30 numbers
+        // have the check digit 10
+        let mut tail = AllUids {
+            pfx: UidPrefix::CHE,
+            next: 99_999_970,
+            remaining: 27,
+        };
+        let last: Vec<String> = tail.by_ref().map(|uid| uid.to_string()).collect();
+        assert_eq!(last.len(), 27);
+        assert_eq!(last.last().unwrap(), "CHE-999.999.996");
+        assert_eq!(tail.len(), 0);
+        assert_eq!(tail.next(), None);
+    }
+
+    #[test]
+    fn test_digit_iter_roundtrip() {
+        for uid in SwissUid::iter_all(UidPrefix::ADM).take(50) {
+            assert_eq!(
+                SwissUid::from_digit_iter(UidPrefix::ADM, uid.iter_digits()),
+                Ok(uid)
+            );
+        }
+        assert_eq!(
+            SwissUid::from_digit_iter(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 5, 1, 0]),
+            Err(UidError::InvalidFormat("UID must have 9 digits", 9))
+        );
+        assert_eq!(
+            SwissUid::from_digit_iter(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 5, 10]),
+            Err(UidError::InvalidFormat("Digits must be between 0 and 9", 8))
+        );
+        assert_eq!(
+            SwissUid::from_digit_iter(UidPrefix::CHE, [0, 1, 0, 3, 2, 2, 5, 5, 7]),
+            Err(UidError::LeadingZeroNotAllowed(0))
+        );
+    }
+
+    #[test]
+    fn test_parse_const_matches_runtime_parser() {
+        use crate::test_vectors::{INVALID, VALID};
+
+        for v in VALID {
+            assert_eq!(
+                SwissUid::parse_const(v.input),
+                SwissUid::new(v.input),
+                "{}",
+                v.input
+            );
+        }
+        for v in INVALID {
+            assert_eq!(
+                SwissUid::parse_const(v.input),
+                SwissUid::new(v.input),
+                "{}",
+                v.input
+            );
+        }
+        for input in ["", "CH", "CHü-109.322.551", "CHE-109.322.55"] {
+            assert_eq!(
+                SwissUid::parse_const(input),
+                SwissUid::new(input),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_digits_const() {
+        const UID: Result<SwissUid, UidError> =
+            SwissUid::from_digits_const(UidPrefix::ADM, [1, 0, 0, 0, 0, 0, 0, 2, 9]);
+        assert_eq!(UID.unwrap().to_string(), "ADM-100.000.029");
+        assert_eq!(
+            SwissUid::from_digits_const(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 5, 11]),
+            Err(UidError::InvalidFormat(
+                "UID digits must be between 0 and 9",
+                8
+            ))
+        );
+        assert_eq!(
+            SwissUid::from_digits_const(UidPrefix::CHE, [1, 0, 9, 3, 2, 2, 5, 10, 1]),
+            Err(UidError::InvalidFormat(
+                "UID digits must be between 0 and 9",
+                7
+            ))
+        );
+        assert_eq!(
+            SwissUid::from_digits_const(UidPrefix::CHE, [0, 1, 0, 3, 2, 2, 5, 5, 7]),
+            Err(UidError::LeadingZeroNotAllowed(0))
+        );
+    }
+
+    #[test]
+    fn test_validate_matches_parser() {
+        use crate::test_vectors::{INVALID, VALID};
+
+        for v in VALID {
+            assert!(is_valid(v.input), "{}", v.input);
+        }
+        for v in INVALID {
+            assert_eq!(
+                validate(v.input),
+                SwissUid::new(v.input).map(|_| ()),
+                "{}",
+                v.input
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_main_and_all_digits() {
+        for uid in SwissUid::iter_all(UidPrefix::CHE).step_by(7919).take(100) {
+            assert_eq!(
+                SwissUid::from_main_digits(uid.prefix(), uid.main_digits()),
+                Ok(uid)
+            );
+            assert_eq!(
+                SwissUid::from_all_digits(uid.prefix(), uid.digits()),
+                Ok(uid)
+            );
+        }
+        assert_eq!(
+            SwissUid::from_main_digits(UidPrefix::CHE, [1, 0, 0, 0, 0, 0, 1, 6]),
+            Err(UidError::InvalidCheckDigit(10, 8))
+        );
+        assert_eq!(
+            SwissUid::from_main_digits(UidPrefix::ADM, [0, 1, 0, 3, 2, 2, 5, 5]),
+            Err(UidError::LeadingZeroNotAllowed(0))
+        );
+    }
+
+    #[test]
+    fn test_u32_roundtrip() {
+        for uid in SwissUid::iter_all(UidPrefix::CHE).step_by(7919).take(100) {
+            assert_eq!(SwissUid::try_from(u32::from(uid)), Ok(uid));
+        }
+        let adm = SwissUid::new("ADM-100.000.029").unwrap();
+        assert_eq!(
+            SwissUid::try_from(adm.as_number()).unwrap().to_string(),
+            "CHE-100.000.029"
+        );
+        assert_eq!(
+            SwissUid::try_from(1_093_225_510u32),
+            Err(UidError::InvalidFormat("UID must have 9 digits", 0))
+        );
+        assert_eq!(
+            SwissUid::try_from(9_322_551u32),
+            Err(UidError::LeadingZeroNotAllowed(0))
+        );
+    }
+
+    #[test]
+    fn test_ord_matches_canonical_strings() {
+        let mut uids: Vec<SwissUid> = UidPrefix::ALL
+            .into_iter()
+            .flat_map(|pfx| SwissUid::iter_all(pfx).step_by(7919).take(100))
+            .collect();
+        uids.reverse();
+        uids.sort();
+        assert!(uids.windows(2).all(|w| w[0].to_string() < w[1].to_string()));
+        assert!(UidPrefix::ADM < UidPrefix::CHE);
+
+        let set: ::std::collections::BTreeSet<SwissUid> = uids.iter().copied().collect();
+        assert!(set.iter().eq(uids.iter()));
+        let hashed: ::std::collections::HashSet<SwissUid> = uids.iter().copied().collect();
+        assert_eq!(hashed.len(), uids.len());
+        assert!(hashed.contains(&uids[0]));
+    }
+
     #[test]
     fn test_eq_uid() {
         let uid1 = SwissUid::new("CHE-109.322.551");
-        assert_eq!(uid1.is_ok(), true);
+        assert!(uid1.is_ok());
         let uid1 = uid1.unwrap();
         let uid2 = SwissUid::new("CHE-109.322.551");
-        assert_eq!(uid2.is_ok(), true);
+        assert!(uid2.is_ok());
         let uid2 = uid2.unwrap();
         assert_eq!(uid1, uid2);
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_clone_uid() {
         let uid1 = SwissUid::new("CHE-109.322.551");
-        assert_eq!(uid1.is_ok(), true);
+        assert!(uid1.is_ok());
         let uid1 = uid1.unwrap();
         let uid2 = uid1.clone();
         assert_eq!(uid1, uid2);
@@ -0,0 +1,74 @@
+//! Exercises the public API of every cargo feature, so a feature combination
+//! which does not compile fails here instead of in downstream crates.
+//!
+//! Run it for each combination of the features:
+//!
+//! ```text
+//! cargo test --test test_features --no-default-features
+//! cargo test --test test_features --no-default-features --features rand_core
+//! cargo test --test test_features --no-default-features --features debug-invariants
+//! cargo test --test test_features
+//! cargo test --test test_features --all-features
+//! ```
+
+use swiss_uid::uid::*;
+
+#[test]
+fn test_without_features() {
+    let uid = SwissUid::new("CHE-109.322.551").unwrap();
+    assert_eq!(SwissUid::try_from_u64(uid.to_u64()), Ok(uid));
+    assert_eq!(uid.verify(), Ok(()));
+    assert_eq!(swiss_uid::uid!("ADM-100.000.029").prefix(), UidPrefix::ADM);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_feature_rand() {
+    use swiss_uid::generate::WeightedBlocks;
+
+    let uid = SwissUid::rand().unwrap();
+    assert_eq!(uid.verify(), Ok(()));
+    let uid = SwissUid::rand_in_range(10_000_000..=10_000_099).unwrap();
+    assert!(uid.to_string().starts_with("CHE-100.000."));
+
+    let blocks = WeightedBlocks::new([(10_000_000..=10_000_099, 1.0)]).unwrap();
+    let uid = SwissUid::rand_with_distribution(&blocks).unwrap();
+    assert!(uid.to_string().starts_with("CHE-100.000."));
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn test_feature_rand_core() {
+    struct Counter(u32);
+    impl rand_core::RngCore for Counter {
+        fn next_u32(&mut self) -> u32 {
+            self.0 = self.0.wrapping_add(7919);
+            self.0
+        }
+        fn next_u64(&mut self) -> u64 {
+            self.next_u32() as u64
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dest)
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    let mut rng = Counter(0);
+    assert_eq!(SwissUid::rand_with_rng(&mut rng).unwrap().verify(), Ok(()));
+    let uid = SwissUid::rand_in_range_with_rng(&mut rng, 99_999_900..=99_999_999).unwrap();
+    assert!(uid.to_string().starts_with("CHE-999.999."));
+}
+
+#[cfg(feature = "debug-invariants")]
+#[test]
+fn test_feature_debug_invariants() {
+    // The invariants are asserted while formatting and converting
+    for uid in SwissUid::iter_all(UidPrefix::CHE).step_by(7919).take(100) {
+        assert_eq!(SwissUid::new(&uid.to_string()), Ok(uid));
+        assert_eq!(SwissUid::try_from_u64(uid.to_u64()), Ok(uid));
+    }
+}
@@ -0,0 +1,111 @@
+//! Feeds the parsers with generated garbage to make sure no input can panic.
+
+use swiss_uid::{
+    extract::find_uids,
+    parse::ParseOptions,
+    qrbill,
+    uid::{SwissUid, UidPrefix},
+};
+
+/// Characters the generated inputs are built from, including multi-byte ones
+/// and the separators of a real UID.
+const ALPHABET: [char; 21] = [
+    'C', 'H', 'E', 'A', 'D', 'M', 'c', 'h', 'e', '0', '1', '5', '9', '-', '.', ' ', 'ü', '€', '😀',
+    '\u{0301}', '\u{200b}',
+];
+
+/// Minimal xorshift generator, enough to get reproducible pseudo-random input.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn parse_everything(input: &str) {
+    let _ = SwissUid::new(input);
+    let _ = SwissUid::parse_canonical(input);
+    let _ = SwissUid::parse_const(input);
+    let _ = swiss_uid::uid::validate(input);
+    let _ = input.parse::<UidPrefix>();
+    let strict = ParseOptions {
+        reject_trailing: true,
+        allow_prefix_aliases: true,
+        require_canonical: true,
+        reject_lowercase: true,
+        default_prefix: Some(UidPrefix::CHE),
+    };
+    let _ = SwissUid::parse_with_report(input, &strict);
+    let _ = SwissUid::parse_with_report(input, &ParseOptions::default());
+    let _ = SwissUid::parse_with_suffix(input);
+    let chars = input.chars().count();
+    for result in [
+        SwissUid::new(input).map(drop),
+        SwissUid::parse_canonical(input).map(drop),
+        SwissUid::parse_const(input).map(drop),
+        SwissUid::parse_with(input, &strict).map(drop),
+        swiss_uid::uid::validate(input),
+        qrbill::parse_s1(input).map(drop),
+    ] {
+        if let Err(err) = result {
+            assert!(err.position() <= chars, "{:?}", input);
+            let _ = err.render_diagnostic(input);
+        }
+    }
+    let _ = find_uids(input).count();
+    let _ = qrbill::parse_s1(input);
+}
+
+#[test]
+fn test_short_inputs_do_not_panic() {
+    let mut input = String::new();
+    for a in ALPHABET {
+        for b in ALPHABET {
+            for c in ALPHABET {
+                input.clear();
+                input.extend([a, b, c]);
+                for end in 0..=input.len() {
+                    if let Some(prefix) = input.get(..end) {
+                        parse_everything(prefix);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_random_inputs_do_not_panic() {
+    let mut rng = XorShift(0x5eed_cafe_f00d_0097);
+    let mut input = String::new();
+    for _ in 0..20_000 {
+        input.clear();
+        let len = rng.next() % 24;
+        input.extend((0..len).map(|_| ALPHABET[(rng.next() % ALPHABET.len() as u64) as usize]));
+        parse_everything(&input);
+    }
+}
+
+#[test]
+fn test_mutated_uids_do_not_panic() {
+    let mut rng = XorShift(0x0123_4567_89ab_cdef);
+    let valid = "IDE-109.322.551 MWST";
+    for _ in 0..20_000 {
+        let mut chars: Vec<char> = valid.chars().collect();
+        let pos = (rng.next() % chars.len() as u64) as usize;
+        let c = ALPHABET[(rng.next() % ALPHABET.len() as u64) as usize];
+        match rng.next() % 3 {
+            0 => chars[pos] = c,
+            1 => chars.insert(pos, c),
+            _ => {
+                chars.remove(pos);
+            }
+        }
+        let input: String = chars.into_iter().collect();
+        parse_everything(&input);
+    }
+}
@@ -3,7 +3,7 @@ use swiss_uid::uid::*;
 #[test]
 fn test_che_uid_from_new_is_valid() {
     let uid = SwissUid::new("CHE-109.322.551");
-    assert_eq!(uid.is_ok(), true);
+    assert!(uid.is_ok());
     let uid = uid.unwrap();
     assert_eq!(uid.to_string(), "CHE-109.322.551");
     assert_eq!(uid.to_string_hr(), "CHE-109.322.551 HR");
@@ -17,3 +17,12 @@ fn test_che_uid_parsed_from_str_is_valid() {
     let uid: SwissUid = "CHE-109.322.551".parse().unwrap();
     assert_eq!(uid.to_string(), "CHE-109.322.551");
 }
+
+#[test]
+fn test_root_reexports_are_the_uid_types() {
+    let uid: swiss_uid::SwissUid = SwissUid::new("CHE-109.322.551").unwrap();
+    let err: swiss_uid::UidError = SwissUid::new("CHE-109.322.552").unwrap_err();
+    assert_eq!(err.kind(), swiss_uid::UidErrorKind::MismatchedCheckDigit);
+    assert!(matches!("CHE".parse(), Ok(swiss_uid::UidPrefix::CHE)));
+    assert_eq!(uid.to_string(), "CHE-109.322.551");
+}